@@ -3,10 +3,22 @@
 use crate::Weak;
 use fallacy_alloc::AllocError;
 use std::alloc::Layout;
+use std::any::Any;
+use std::borrow::Borrow;
+use std::clone::CloneToUninit;
+use std::collections::hash_map::RandomState;
+use std::error::Error;
+use std::ffi::CStr;
 use std::fmt;
-use std::hash::Hash;
-use std::ops::Deref;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::io;
+use std::marker::Unsize;
+use std::mem::MaybeUninit;
+use std::ops::{CoerceUnsized, Deref, DispatchFromDyn};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc as StdArc;
+use std::task::{Wake, Waker};
 
 /// A thread-safe reference-counting pointer. 'Arc' stands for 'Atomically
 /// Reference Counted'.
@@ -18,7 +30,7 @@ use std::sync::Arc as StdArc;
 /// source `Arc`, while increasing a reference count. When the last `Arc`
 /// pointer to a given allocation is destroyed, the value stored in that allocation (often
 /// referred to as "inner value") is also dropped.
-#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(Ord, PartialOrd)]
 #[repr(transparent)]
 pub struct Arc<T: ?Sized>(StdArc<T>);
 
@@ -30,6 +42,109 @@ impl<T> Arc<T> {
             StdArc::try_new(data).map_err(|_| AllocError::new(Layout::new::<T>()))?
         ))
     }
+
+    /// Constructs a new `Pin<Arc<T>>`, returning an error if allocation fails.
+    ///
+    /// If `T` does not implement `Unpin`, then `data` will be pinned in memory and unable
+    /// to be moved.
+    #[inline]
+    pub fn try_pin(data: T) -> Result<Pin<Arc<T>>, AllocError> {
+        // SAFETY: a `Pin<Arc<T>>` gives no access to the `Arc<T>` or to `&mut T`, so the
+        // inner value can only be moved through an unpinned `Arc<T>` to the same allocation.
+        // The ways to get one are all unsafe:
+        // - `Pin::into_inner_unchecked`, whose contract forbids moving the value.
+        // - `Arc::clone_from_inner`, which requires the allocation not to be pinned unless
+        //   `T: Unpin`.
+        // - `Arc::from_raw`, `Arc::increment_strong_count` and `BorrowedArc::from_raw`, whose
+        //   pointer has to come from `Arc::into_raw` or `Arc::as_ptr`, which take an
+        //   unpinned `Arc`.
+        Ok(unsafe { Pin::new_unchecked(Arc::try_new(data)?) })
+    }
+
+    /// Returns the inner value, if the `Arc` has exactly one strong reference.
+    ///
+    /// Otherwise, an [`Err`] is returned with the same `Arc` that was passed in.
+    #[inline]
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        StdArc::try_unwrap(this.0).map_err(Arc)
+    }
+
+    /// Makes a mutable reference into the given `Arc`.
+    ///
+    /// If there are other `Arc` or [`Weak`] pointers to the same allocation, then `f` is
+    /// called to produce a copy of the inner value, which is moved into a new allocation
+    /// to ensure unique ownership.
+    ///
+    /// Returns an error if `f` fails or if allocating the new `Arc` fails.
+    #[inline]
+    pub fn make_mut_with<F, E>(this: &mut Self, f: F) -> Result<&mut T, E>
+    where
+        F: FnOnce(&T) -> Result<T, E>,
+        E: From<AllocError>,
+    {
+        if StdArc::get_mut(&mut this.0).is_none() {
+            *this = Arc::try_new(f(this)?)?;
+        }
+        // SAFETY: `this` is the only pointer to its allocation, either it has been checked
+        // above or it has just been replaced by a new allocation.
+        Ok(unsafe { StdArc::get_mut_unchecked(&mut this.0) })
+    }
+
+    /// Transforms the inner value of a unique `Arc` with `f`, reusing the allocation
+    /// when `T` and `U` have the same size and alignment.
+    ///
+    /// If there are other `Arc` or [`Weak`] pointers to the same allocation, `f` is not
+    /// called and `this` is returned in the outer `Err`. Otherwise the inner value is
+    /// moved into `f`, and its result is stored in the existing allocation if the layouts
+    /// match, or in a new allocation if they do not. The inner `Err` is returned if `f`
+    /// fails or if allocating the new `Arc` fails.
+    pub fn try_map<U, E, F>(this: Self, f: F) -> Result<Result<Arc<U>, E>, Self>
+    where
+        F: FnOnce(T) -> Result<U, E>,
+        E: From<AllocError>,
+    {
+        let mut this = this;
+        if StdArc::get_mut(&mut this.0).is_none() {
+            return Err(this);
+        }
+        // SAFETY: `MaybeUninit<T>` has the same layout as `T`.
+        let old = unsafe { StdArc::from_raw(StdArc::into_raw(this.0).cast::<MaybeUninit<T>>()) };
+        // SAFETY: the value is initialized, and `old` does not drop it again.
+        let value = unsafe { old.as_ptr().read() };
+        let mut new = if Layout::new::<T>() == Layout::new::<U>() {
+            // SAFETY: `MaybeUninit<U>` has the same size and alignment as `MaybeUninit<T>`.
+            unsafe { StdArc::from_raw(StdArc::into_raw(old).cast::<MaybeUninit<U>>()) }
+        } else {
+            drop(old);
+            match StdArc::try_new_uninit() {
+                Ok(new) => new,
+                Err(_) => return Ok(Err(AllocError::new(Layout::new::<U>()).into())),
+            }
+        };
+        let data = match f(value) {
+            Ok(data) => data,
+            Err(e) => return Ok(Err(e)),
+        };
+        // SAFETY: `new` is the only pointer to its allocation, it was either checked to be
+        // unique above or has just been allocated.
+        unsafe { StdArc::get_mut_unchecked(&mut new).write(data) };
+        // SAFETY: the value has just been initialized.
+        Ok(Ok(Arc(unsafe { StdArc::from_raw(StdArc::into_raw(new).cast::<U>()) })))
+    }
+}
+
+impl<T: ?Sized + CloneToUninit> Arc<T> {
+    /// Constructs a new `Arc<T>` with a clone of `value`, returning an error if
+    /// allocation fails.
+    ///
+    /// This is the fallible counterpart of the `From<&[T]>`, `From<&str>` and
+    /// `From<&CStr>` conversions of std's `Arc`, and works for any clonable value.
+    #[inline]
+    pub fn try_clone_from_ref(value: &T) -> Result<Arc<T>, AllocError> {
+        StdArc::try_clone_from_ref(value)
+            .map(Arc)
+            .map_err(|_| AllocError::new(Layout::for_value(value)))
+    }
 }
 
 impl<T: ?Sized> Arc<T> {
@@ -43,6 +158,124 @@ impl<T: ?Sized> Arc<T> {
         Arc(a)
     }
 
+    /// Consumes the `Arc`, returning the wrapped pointer.
+    ///
+    /// To avoid a memory leak the pointer must be converted back to an `Arc` using
+    /// [`Arc::from_raw`].
+    #[must_use = "losing the pointer will leak memory"]
+    #[inline]
+    pub fn into_raw(this: Self) -> *const T {
+        StdArc::into_raw(this.0)
+    }
+
+    /// Provides a raw pointer to the data.
+    ///
+    /// The counts are not affected in any way and the `Arc` is not consumed. The pointer is
+    /// valid for as long as there are strong counts in the `Arc`.
+    #[must_use]
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *const T {
+        StdArc::as_ptr(&this.0)
+    }
+
+    /// Constructs an `Arc<T>` from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// The raw pointer must have been previously returned by a call to
+    /// [`Arc<U>::into_raw`][into_raw] with the following requirements:
+    ///
+    /// * If `U` is sized, it must have the same size and alignment as `T`. This
+    ///   is trivially true if `U` is `T`.
+    /// * If `U` is unsized, its data pointer must have the same size and
+    ///   alignment as `T`. This is trivially true if `Arc<U>` was constructed
+    ///   through `Arc<T>` and then converted to `Arc<U>` through an unsized
+    ///   coercion.
+    ///
+    /// The user of `from_raw` has to make sure a specific value of `T` is only
+    /// dropped once.
+    ///
+    /// In debug builds, `ptr` is checked to be non-null and aligned. Whether the
+    /// allocation is still alive, or the pointer has already been turned back into an
+    /// `Arc`, is not detected.
+    ///
+    /// [into_raw]: Arc::into_raw
+    #[track_caller]
+    #[inline]
+    pub unsafe fn from_raw(ptr: *const T) -> Self {
+        debug_check_raw(ptr);
+        Arc(StdArc::from_raw(ptr))
+    }
+
+    /// Increments the strong reference count on the `Arc<T>` associated with the
+    /// provided pointer by one.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must have been obtained through `Arc::into_raw` and must satisfy the
+    /// same layout requirements specified in [`Arc::from_raw`].
+    /// The associated `Arc` instance must be valid (i.e. the strong count must be at
+    /// least 1) for the duration of this method.
+    ///
+    /// In debug builds, `ptr` is checked to be non-null and aligned, as in
+    /// [`Arc::from_raw`]. Whether the allocation is still alive is not detected.
+    #[track_caller]
+    #[inline]
+    pub unsafe fn increment_strong_count(ptr: *const T) {
+        debug_check_raw(ptr);
+        StdArc::increment_strong_count(ptr);
+        #[cfg(feature = "watermark")]
+        {
+            let arc = std::mem::ManuallyDrop::new(StdArc::from_raw(ptr));
+            crate::watermark::check::<T>(StdArc::strong_count(&arc));
+        }
+    }
+
+    /// Decrements the strong reference count on the `Arc<T>` associated with the
+    /// provided pointer by one.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must have been obtained through `Arc::into_raw` and must satisfy the
+    /// same layout requirements specified in [`Arc::from_raw`].
+    /// The associated `Arc` instance must be valid (i.e. the strong count must be at
+    /// least 1) when invoking this method. This method can be used to release the final
+    /// `Arc` and backing storage, but **should not** be called after the final `Arc` has been
+    /// released.
+    ///
+    /// In debug builds, `ptr` is checked to be non-null and aligned, as in
+    /// [`Arc::from_raw`]. Whether the allocation is still alive is not detected.
+    #[track_caller]
+    #[inline]
+    pub unsafe fn decrement_strong_count(ptr: *const T) {
+        debug_check_raw(ptr);
+        StdArc::decrement_strong_count(ptr)
+    }
+
+    /// Creates a new `Arc` pointing to the allocation that `data` is the inner value of,
+    /// increasing the strong reference count.
+    ///
+    /// This recovers an owning handle from a plain reference, for example in a callback
+    /// that only receives `&T`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be a reference to the inner value of an allocation owned by an `Arc`
+    /// from this crate (or from std), such as one returned by deref or [`Arc::as_ptr`].
+    /// A reference to a field of the inner value, or to a value that is not in an `Arc`
+    /// at all, is not allowed.
+    ///
+    /// Unless `T: Unpin`, the allocation must not be pinned, for example through
+    /// [`Arc::try_pin`]. The returned `Arc` is not pinned, so it could be used to move the
+    /// inner value out with [`Arc::try_unwrap`].
+    #[track_caller]
+    #[inline]
+    pub unsafe fn clone_from_inner(data: &T) -> Arc<T> {
+        let ptr: *const T = data;
+        Arc::increment_strong_count(ptr);
+        Arc::from_raw(ptr)
+    }
+
     /// Creates a new [`Weak`] pointer to this allocation.
     #[must_use = "this returns a new `Weak` pointer, \
                   without modifying the original `Arc`"]
@@ -77,6 +310,34 @@ impl<T: ?Sized> Arc<T> {
         StdArc::strong_count(&this.0)
     }
 
+    /// Gets the number of strong and [`Weak`] pointers to this allocation, as
+    /// `(strong, weak)`.
+    ///
+    /// The two counters live in separate atomics, so they are read one after the other.
+    /// The strong count is read again after the weak count, and the reads are retried
+    /// until it hasn't changed, so the pair is one that both counters held at the same
+    /// moment unless the strong count changed and changed back in between. The strong
+    /// count is never reported as 0, since `this` itself is a strong pointer.
+    ///
+    /// # Safety
+    ///
+    /// This method by itself is safe, but using it correctly requires extra care.
+    /// Another thread can change either count at any time,
+    /// including potentially between calling this method and acting on the result.
+    #[must_use]
+    #[inline]
+    pub fn counts(this: &Self) -> (usize, usize) {
+        let mut strong = StdArc::strong_count(&this.0);
+        loop {
+            let weak = StdArc::weak_count(&this.0);
+            let again = StdArc::strong_count(&this.0);
+            if again == strong {
+                return (strong, weak);
+            }
+            strong = again;
+        }
+    }
+
     /// Returns `true` if the two `Arc`s point to the same allocation
     /// (in a vein similar to [`std::ptr::eq`]).
     #[must_use]
@@ -84,6 +345,196 @@ impl<T: ?Sized> Arc<T> {
     pub fn ptr_eq(this: &Self, other: &Self) -> bool {
         StdArc::ptr_eq(&this.0, &other.0)
     }
+
+    /// Returns an identifier for the allocation of this `Arc`, for correlating log lines
+    /// about the same shared value.
+    ///
+    /// Two `Arc`s get the same identifier exactly when [`Arc::ptr_eq`] returns `true`.
+    /// The identifier is derived from the address of the allocation, combined with a
+    /// random key chosen once per process, so it can't be turned back into the address
+    /// without the key. It is only unique among live allocations:
+    /// once the last `Arc` and [`Weak`] are dropped, a new allocation may get the same
+    /// identifier.
+    #[must_use]
+    #[inline]
+    pub fn allocation_id(this: &Self) -> u64 {
+        // A bijective mix of the keyed address (the SplitMix64 finalizer), so distinct
+        // addresses keep distinct identifiers.
+        let mut id = StdArc::as_ptr(&this.0).cast::<u8>() as usize as u64 ^ allocation_id_key();
+        id = (id ^ (id >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        id = (id ^ (id >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        id ^ (id >> 31)
+    }
+
+    /// Returns a mutable reference into the given `Arc`, if there are no other `Arc` or
+    /// [`Weak`] pointers to the same allocation.
+    ///
+    /// Returns [`None`] otherwise, because it is not safe to mutate a shared value.
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        StdArc::get_mut(&mut this.0)
+    }
+
+    /// Calls `f` with a mutable reference to the inner value if there are no other `Arc`
+    /// or [`Weak`] pointers to the same allocation.
+    ///
+    /// Returns `true` if `f` was called, or `false` if the allocation is shared.
+    #[inline]
+    pub fn map_unique<F>(this: &mut Self, f: F) -> bool
+    where
+        F: FnOnce(&mut T),
+    {
+        match StdArc::get_mut(&mut this.0) {
+            Some(data) => {
+                f(data);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Converts the `Arc` into an `Arc` of a type that `T` unsizes to, such as a
+    /// supertrait object.
+    ///
+    /// This is the same as the implicit unsized coercion, written as a function for
+    /// places where inference can't see the target type.
+    #[inline]
+    pub fn upcast<U: ?Sized>(this: Self) -> Arc<U>
+    where
+        T: Unsize<U>,
+    {
+        this
+    }
+}
+
+/// Returns the random key that `Arc::allocation_id` mixes into addresses.
+fn allocation_id_key() -> u64 {
+    static KEY: AtomicU64 = AtomicU64::new(0);
+    let key = KEY.load(Ordering::Relaxed);
+    if key != 0 {
+        return key;
+    }
+    // Zero marks the key as unset, so the lowest bit is always set.
+    let new = RandomState::new().build_hasher().finish() | 1;
+    match KEY.compare_exchange(0, new, Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => new,
+        Err(key) => key,
+    }
+}
+
+/// Checks in debug builds that `ptr` is non-null and aligned, as a pointer returned by
+/// `Arc::into_raw` is.
+///
+/// The allocation is not read, so this can't tell whether it is still alive.
+///
+/// # Safety
+///
+/// The metadata of `ptr` must be valid for `T`, as required by `align_of_val_raw`.
+#[track_caller]
+#[inline]
+unsafe fn debug_check_raw<T: ?Sized>(ptr: *const T) {
+    if cfg!(debug_assertions) {
+        assert!(!ptr.is_null(), "null pointer passed as an `Arc` pointer");
+        let align = std::mem::align_of_val_raw(ptr);
+        assert!(
+            ptr.cast::<u8>() as usize % align == 0,
+            "misaligned pointer passed as an `Arc` pointer"
+        );
+    }
+}
+
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<Arc<U>> for Arc<T> {}
+
+impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Arc<U>> for Arc<T> {}
+
+impl Arc<dyn Any + Send + Sync> {
+    /// Attempts to downcast the `Arc<dyn Any + Send + Sync>` to a concrete type.
+    ///
+    /// Returns the original `Arc` if the inner value is not of type `T`.
+    #[inline]
+    pub fn downcast<T>(self) -> Result<Arc<T>, Self>
+    where
+        T: Any + Send + Sync,
+    {
+        self.0.downcast().map(Arc).map_err(Arc)
+    }
+}
+
+impl<T: ?Sized> Clone for Arc<T> {
+    /// Makes a clone of the `Arc` pointer.
+    ///
+    /// This creates another pointer to the same allocation, increasing the
+    /// strong reference count.
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    fn clone(&self) -> Arc<T> {
+        let arc = Arc(self.0.clone());
+        #[cfg(feature = "watermark")]
+        crate::watermark::check::<T>(StdArc::strong_count(&arc.0));
+        arc
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for Arc<T> {
+    /// Equality for two `Arc`s.
+    ///
+    /// Two `Arc`s are equal if their inner values are equal, even if they are
+    /// stored in different allocation.
+    ///
+    /// If `T` also implements `Eq` (implying reflexivity of equality),
+    /// two `Arc`s that point to the same allocation are always equal, and are
+    /// compared by pointer without comparing the inner values.
+    #[inline]
+    fn eq(&self, other: &Arc<T>) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for Arc<T> {}
+
+impl<T: ?Sized + Hash> Hash for Arc<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl Default for Arc<str> {
+    /// Creates an empty `str` inside an `Arc`.
+    ///
+    /// This does not allocate, the returned `Arc` points to a shared static allocation.
+    #[inline]
+    fn default() -> Self {
+        Arc(StdArc::default())
+    }
+}
+
+impl Default for Arc<CStr> {
+    /// Creates an empty `CStr` inside an `Arc`.
+    ///
+    /// This does not allocate, the returned `Arc` points to a shared static allocation.
+    #[inline]
+    fn default() -> Self {
+        Arc(StdArc::default())
+    }
+}
+
+impl<T> Default for Arc<[T]> {
+    /// Creates an empty `[T]` inside an `Arc`.
+    ///
+    /// This does not allocate, the returned `Arc` points to a shared static allocation.
+    /// Std can only do so for element types aligned to at most 16 bytes, so more
+    /// strictly aligned element types are rejected at compile time.
+    #[inline]
+    fn default() -> Self {
+        const {
+            assert!(
+                std::mem::align_of::<T>() <= 16,
+                "element type is aligned to more than 16 bytes"
+            )
+        };
+        Arc(StdArc::default())
+    }
 }
 
 impl<T: ?Sized> Deref for Arc<T> {
@@ -95,6 +546,13 @@ impl<T: ?Sized> Deref for Arc<T> {
     }
 }
 
+impl<T: ?Sized> Borrow<T> for Arc<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.0.borrow()
+    }
+}
+
 impl<T: ?Sized> AsRef<T> for Arc<T> {
     #[inline]
     fn as_ref(&self) -> &T {
@@ -102,6 +560,29 @@ impl<T: ?Sized> AsRef<T> for Arc<T> {
     }
 }
 
+impl From<Arc<str>> for Arc<[u8]> {
+    /// Converts an `Arc<str>` into an `Arc<[u8]>` pointing to the same allocation.
+    #[inline]
+    fn from(arc: Arc<str>) -> Self {
+        Arc(StdArc::from(arc.0))
+    }
+}
+
+impl<W: Wake + Send + Sync + 'static> From<Arc<W>> for Waker {
+    /// Uses a [`Wake`]-able type as a [`Waker`], without allocating.
+    #[inline]
+    fn from(waker: Arc<W>) -> Waker {
+        Waker::from(waker.0)
+    }
+}
+
+impl<T: ?Sized + Error> Error for Arc<T> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Error::source(&**self)
+    }
+}
+
 impl<T: ?Sized + fmt::Display> fmt::Display for Arc<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -123,6 +604,118 @@ impl<T: ?Sized> fmt::Pointer for Arc<T> {
     }
 }
 
+impl<T: ?Sized + fmt::LowerHex> fmt::LowerHex for Arc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::UpperHex> fmt::UpperHex for Arc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Octal> fmt::Octal for Arc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Octal::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Binary> fmt::Binary for Arc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Binary::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::LowerExp> fmt::LowerExp for Arc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerExp::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::UpperExp> fmt::UpperExp for Arc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperExp::fmt(&**self, f)
+    }
+}
+
+impl<R: ?Sized> io::Read for Arc<R>
+where
+    for<'a> &'a R: io::Read,
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&**self).read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        (&**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (&**self).read_to_end(buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        (&**self).read_exact(buf)
+    }
+}
+
+impl<W: ?Sized> io::Write for Arc<W>
+where
+    for<'a> &'a W: io::Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&**self).write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        (&**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        (&**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        (&**self).write_all(buf)
+    }
+}
+
+impl<S: ?Sized> io::Seek for Arc<S>
+where
+    for<'a> &'a S: io::Seek,
+{
+    #[inline]
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        (&**self).seek(pos)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        (&**self).stream_position()
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serde {
     use crate::Arc;
@@ -155,3 +748,282 @@ mod serde {
         }
     }
 }
+
+#[cfg(feature = "schemars")]
+mod schemars {
+    use crate::Arc;
+    use schemars::{JsonSchema, Schema, SchemaGenerator};
+    use std::borrow::Cow;
+
+    impl<T> JsonSchema for Arc<T>
+    where
+        T: ?Sized + JsonSchema,
+    {
+        #[inline]
+        fn inline_schema() -> bool {
+            T::inline_schema()
+        }
+
+        #[inline]
+        fn schema_name() -> Cow<'static, str> {
+            T::schema_name()
+        }
+
+        #[inline]
+        fn schema_id() -> Cow<'static, str> {
+            T::schema_id()
+        }
+
+        #[inline]
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            T::json_schema(generator)
+        }
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck {
+    use crate::{Arc, ProjectedArc};
+    use bytemuck::{Pod, PodCastError, TransparentWrapper};
+    use std::mem;
+    use std::ptr;
+    use std::sync::Arc as StdArc;
+
+    /// The result of a slice cast, which gives back the original `Arc` on error.
+    type CastResult<T, A> = Result<T, (PodCastError, Arc<[A]>)>;
+
+    impl<T: ?Sized> Arc<T> {
+        /// Converts an `Arc<T>` into an `Arc` of a transparent wrapper of `T`, without
+        /// reallocating.
+        #[inline]
+        pub fn wrap<W>(this: Self) -> Arc<W>
+        where
+            W: ?Sized + TransparentWrapper<T>,
+        {
+            const {
+                assert!(
+                    mem::size_of::<*const T>() == mem::size_of::<*const W>(),
+                    "wrapper and wrapped type have different pointer metadata"
+                )
+            };
+            let ptr = StdArc::into_raw(this.into_std());
+            // SAFETY: `W` is a `repr(transparent)` wrapper of `T`, so the pointers have the
+            // same metadata and the allocations the same layout.
+            Arc::from_std(unsafe { StdArc::from_raw(mem::transmute_copy::<*const T, *const W>(&ptr)) })
+        }
+
+        /// Converts an `Arc` of a transparent wrapper of `T` into an `Arc<T>`, without
+        /// reallocating.
+        #[inline]
+        pub fn peel<W>(arc: Arc<W>) -> Self
+        where
+            W: ?Sized + TransparentWrapper<T>,
+        {
+            const {
+                assert!(
+                    mem::size_of::<*const T>() == mem::size_of::<*const W>(),
+                    "wrapper and wrapped type have different pointer metadata"
+                )
+            };
+            let ptr = StdArc::into_raw(arc.into_std());
+            // SAFETY: as in `Arc::wrap`.
+            Arc::from_std(unsafe { StdArc::from_raw(mem::transmute_copy::<*const W, *const T>(&ptr)) })
+        }
+    }
+
+    impl<A: Pod> Arc<[A]> {
+        /// Reinterprets an `Arc<[A]>` as an `Arc<[B]>` of the same bytes, without
+        /// reallocating.
+        ///
+        /// `A` and `B` must have the same alignment, because the allocation is freed with
+        /// the layout of `[B]`. Returns the error and the original `Arc` if the alignments
+        /// differ, or if the bytes can't be split evenly into elements of `B`. Use
+        /// [`Arc::try_cast_slice_projected`] for types with different alignments.
+        pub fn try_cast_slice<B: Pod>(this: Self) -> CastResult<Arc<[B]>, A> {
+            if mem::align_of::<A>() != mem::align_of::<B>() {
+                return Err((PodCastError::AlignmentMismatch, this));
+            }
+            let len = if mem::size_of::<A>() == mem::size_of::<B>() {
+                this.len()
+            } else if mem::size_of::<A>() == 0 || mem::size_of::<B>() == 0 {
+                return Err((PodCastError::SizeMismatch, this));
+            } else {
+                let bytes = mem::size_of_val::<[A]>(&this);
+                if bytes % mem::size_of::<B>() != 0 {
+                    return Err((PodCastError::OutputSliceWouldHaveSlop, this));
+                }
+                bytes / mem::size_of::<B>()
+            };
+            let ptr = StdArc::into_raw(this.into_std()).cast::<B>();
+            // SAFETY: `[B]` with `len` elements has the same size and alignment as the
+            // original slice, and any bit pattern of it is a valid `B`.
+            Ok(Arc::from_std(unsafe {
+                StdArc::from_raw(ptr::slice_from_raw_parts(ptr, len))
+            }))
+        }
+
+        /// Views the bytes of an `Arc<[A]>` as a `[B]`, keeping the original allocation
+        /// alive.
+        ///
+        /// Unlike [`Arc::try_cast_slice`], `A` and `B` may have different alignments, for
+        /// example to view a byte buffer as a slice of `u32`. Returns the error and the
+        /// original `Arc` if the data is not aligned for `B`, or if the bytes can't be split
+        /// evenly into elements of `B`.
+        pub fn try_cast_slice_projected<B: Pod>(this: Self) -> CastResult<ProjectedArc<[A], [B]>, A> {
+            if let Err(e) = bytemuck::try_cast_slice::<A, B>(&this) {
+                return Err((e, this));
+            }
+            Ok(Arc::project(this, bytemuck::cast_slice))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::test_util::{CountingDrop, DropCounter};
+        use crate::Arc;
+        use bytemuck::{PodCastError, TransparentWrapper};
+
+        #[repr(transparent)]
+        struct Wrapper<T: ?Sized>(T);
+
+        // SAFETY: `Wrapper` is a `repr(transparent)` wrapper of `T`.
+        unsafe impl<T: ?Sized> TransparentWrapper<T> for Wrapper<T> {}
+
+        #[test]
+        fn wrap_and_peel_sized() {
+            let counter = DropCounter::new();
+            let arc = Arc::try_new(counter.wrap(3u32)).unwrap();
+            let ptr = Arc::as_ptr(&arc).cast::<u8>();
+            let wrapped: Arc<Wrapper<CountingDrop<'_, u32>>> = Arc::wrap(arc);
+            assert_eq!(*(*wrapped).0, 3);
+            assert_eq!(Arc::as_ptr(&wrapped).cast::<u8>(), ptr);
+            let peeled = Arc::<CountingDrop<'_, u32>>::peel(wrapped);
+            assert_eq!(**peeled, 3);
+            assert_eq!(counter.dropped(), 0);
+            drop(peeled);
+            assert_eq!(counter.dropped(), 1);
+        }
+
+        #[test]
+        fn wrap_and_peel_unsized() {
+            let arc: Arc<str> = Arc::try_clone_from_ref("shared").unwrap();
+            let wrapped: Arc<Wrapper<str>> = Arc::wrap(arc);
+            assert_eq!(&(*wrapped).0, "shared");
+            let peeled = Arc::<str>::peel(wrapped);
+            assert_eq!(&*peeled, "shared");
+        }
+
+        fn bytes(data: &[u8]) -> Arc<[u8]> {
+            Arc::try_clone_from_ref(data).unwrap()
+        }
+
+        #[test]
+        fn cast_slice_same_alignment() {
+            let floats: Arc<[f32]> = Arc::try_clone_from_ref(&[1.0f32, 2.0][..]).unwrap();
+            let ptr = Arc::as_ptr(&floats).cast::<u8>();
+            let ints = Arc::try_cast_slice::<u32>(floats).unwrap();
+            assert_eq!(&*ints, &[0x3f80_0000, 0x4000_0000]);
+            assert_eq!(Arc::as_ptr(&ints).cast::<u8>(), ptr);
+
+            let pairs: Arc<[[u16; 2]]> = Arc::try_clone_from_ref(&[[1u16, 2], [3, 4]][..]).unwrap();
+            let flat = Arc::try_cast_slice::<u16>(pairs).unwrap();
+            assert_eq!(&*flat, &[1, 2, 3, 4]);
+            let pairs = Arc::try_cast_slice::<[u16; 2]>(flat).unwrap();
+            assert_eq!(&*pairs, &[[1, 2], [3, 4]]);
+        }
+
+        #[test]
+        fn cast_slice_rejects_alignment_mismatch() {
+            let (e, arc) = Arc::try_cast_slice::<u32>(bytes(&[0; 8])).unwrap_err();
+            assert_eq!(e, PodCastError::AlignmentMismatch);
+            assert_eq!(arc.len(), 8);
+        }
+
+        #[test]
+        fn cast_slice_rejects_slop() {
+            let pairs: Arc<[[u8; 2]]> = Arc::try_clone_from_ref(&[[0u8; 2]; 3][..]).unwrap();
+            let (e, arc) = Arc::try_cast_slice::<[u8; 4]>(pairs).unwrap_err();
+            assert_eq!(e, PodCastError::OutputSliceWouldHaveSlop);
+            assert_eq!(arc.len(), 3);
+        }
+
+        #[test]
+        fn cast_slice_projected() {
+            let arc = bytes(&[1, 0, 0, 0, 2, 0, 0, 0]);
+            let ints = Arc::try_cast_slice_projected::<u32>(arc.clone()).unwrap();
+            assert_eq!(
+                &*ints,
+                &[u32::from_ne_bytes([1, 0, 0, 0]), u32::from_ne_bytes([2, 0, 0, 0])]
+            );
+            assert_eq!(Arc::strong_count(&arc), 2);
+
+            let (e, _) = Arc::try_cast_slice_projected::<u32>(bytes(&[0; 3])).unwrap_err();
+            assert_eq!(e, PodCastError::OutputSliceWouldHaveSlop);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::{AllocError, Arc};
+
+    #[test]
+    fn try_map_same_layout_reuses_allocation() {
+        let arc = Arc::try_new(5u32).unwrap();
+        let ptr = Arc::as_ptr(&arc).cast::<u8>();
+        let mapped = Arc::try_map(arc, |v| Ok::<_, AllocError>(-(v as i32)))
+            .ok()
+            .unwrap()
+            .unwrap();
+        assert_eq!(*mapped, -5);
+        assert_eq!(Arc::as_ptr(&mapped).cast::<u8>(), ptr);
+    }
+
+    #[test]
+    fn try_map_different_layout() {
+        let counter = DropCounter::new();
+        let arc = Arc::try_new(counter.wrap(7u8)).unwrap();
+        let mapped = Arc::try_map(arc, |v| Ok::<_, AllocError>([u64::from(*v); 3]))
+            .ok()
+            .unwrap()
+            .unwrap();
+        assert_eq!(*mapped, [7; 3]);
+        assert_eq!(counter.dropped(), 1);
+        assert_eq!(counter.live(), 0);
+    }
+
+    #[test]
+    fn try_map_error_drops_value_once() {
+        let counter = DropCounter::new();
+        let arc = Arc::try_new(counter.wrap(1u32)).unwrap();
+        let result = Arc::try_map(arc, |_| {
+            Err::<u32, _>(AllocError::new(std::alloc::Layout::new::<u32>()))
+        });
+        assert!(matches!(result, Ok(Err(_))));
+        assert_eq!(counter.created(), 1);
+        assert_eq!(counter.dropped(), 1);
+    }
+
+    #[test]
+    fn try_map_shared_returns_input() {
+        let counter = DropCounter::new();
+        let arc = Arc::try_new(counter.wrap(1u32)).unwrap();
+        let other = arc.clone();
+        let mut called = false;
+        let result = Arc::try_map(arc, |v| {
+            called = true;
+            Ok::<_, AllocError>(*v)
+        });
+        let arc = result.err().unwrap();
+        assert!(!called);
+        assert!(Arc::ptr_eq(&arc, &other));
+        assert_eq!(counter.live(), 1);
+
+        let weak = Arc::downgrade(&other);
+        drop(arc);
+        assert!(Arc::try_map(other, |v| Ok::<_, AllocError>(*v)).is_err());
+        drop(weak);
+        assert_eq!(counter.live(), 0);
+    }
+}
@@ -0,0 +1,67 @@
+//! Borrowed views of an `Arc` owned elsewhere.
+
+use crate::Arc;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+
+/// A view of an [`Arc`] that is owned by someone else, created from a raw pointer.
+///
+/// `BorrowedArc` derefs to `Arc<T>` without touching the reference counts, and calling
+/// `clone` on the deref result produces a real, owning `Arc<T>`. Dropping a
+/// `BorrowedArc` does not decrement the strong count.
+///
+/// This is useful for FFI callbacks that receive a pointer obtained from
+/// [`Arc::into_raw`] or [`Arc::as_ptr`] while the caller keeps ownership of the `Arc`.
+pub struct BorrowedArc<'a, T: ?Sized> {
+    arc: ManuallyDrop<Arc<T>>,
+    _marker: PhantomData<&'a Arc<T>>,
+}
+
+impl<'a, T: ?Sized> BorrowedArc<'a, T> {
+    /// Creates a `BorrowedArc` from a raw pointer, without changing any reference count.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must satisfy the requirements of [`Arc::from_raw`], and the allocation
+    /// must keep a strong count of at least 1, held elsewhere, for the whole lifetime `'a`.
+    #[track_caller]
+    #[inline]
+    pub unsafe fn from_raw(ptr: *const T) -> Self {
+        BorrowedArc {
+            arc: ManuallyDrop::new(Arc::from_raw(ptr)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new owning [`Arc`] pointing to the same allocation.
+    #[must_use]
+    #[inline]
+    pub fn to_arc(this: &Self) -> Arc<T> {
+        Arc::clone(&this.arc)
+    }
+}
+
+impl<T: ?Sized> Deref for BorrowedArc<'_, T> {
+    type Target = Arc<T>;
+
+    #[inline]
+    fn deref(&self) -> &Arc<T> {
+        &self.arc
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for BorrowedArc<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.arc, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for BorrowedArc<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.arc, f)
+    }
+}
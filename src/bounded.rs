@@ -0,0 +1,213 @@
+//! A thread-safe reference-counting pointer with a limited number of handles.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Bounded<T: ?Sized> {
+    handles: AtomicUsize,
+    max: usize,
+    data: T,
+}
+
+/// A thread-safe reference-counting pointer whose number of handles is bounded.
+///
+/// Unlike [`Arc`], a `BoundedArc<T>` is not [`Clone`]. New handles are created with
+/// [`try_clone`], which fails once `max` handles to the same allocation exist. Dropping
+/// a handle makes room for another one, so the handles act as permits for the shared
+/// value.
+///
+/// `try_clone` is an associated function, which means that you have to call it as
+/// `BoundedArc::try_clone(&a)` instead of `a.try_clone()`. This is so that there is no
+/// conflict with a method on the inner type.
+///
+/// [`try_clone`]: BoundedArc::try_clone
+pub struct BoundedArc<T: ?Sized>(Arc<Bounded<T>>);
+
+impl<T> BoundedArc<T> {
+    /// Constructs a new `BoundedArc<T>` allowing at most `max` handles, returning an
+    /// error if allocation fails.
+    ///
+    /// The returned handle counts towards `max`, so a `max` of 0 behaves like 1.
+    #[inline]
+    pub fn try_new(data: T, max: usize) -> Result<BoundedArc<T>, AllocError> {
+        Ok(BoundedArc(Arc::try_new(Bounded {
+            handles: AtomicUsize::new(1),
+            max: max.max(1),
+            data,
+        })?))
+    }
+}
+
+impl<T: ?Sized> BoundedArc<T> {
+    /// Creates another handle to the same allocation, returning an error if `max`
+    /// handles already exist.
+    #[cfg_attr(feature = "watermark", track_caller)]
+    pub fn try_clone(this: &Self) -> Result<BoundedArc<T>, HandleLimitError> {
+        let inner = &*this.0;
+        let mut handles = inner.handles.load(Ordering::Relaxed);
+        loop {
+            if handles >= inner.max {
+                return Err(HandleLimitError { max: inner.max });
+            }
+            match inner
+                .handles
+                .compare_exchange_weak(handles, handles + 1, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => return Ok(BoundedArc(this.0.clone())),
+                Err(actual) => handles = actual,
+            }
+        }
+    }
+
+    /// Gets the number of `BoundedArc` handles to this allocation.
+    ///
+    /// # Safety
+    ///
+    /// This method by itself is safe, but using it correctly requires extra care.
+    /// Another thread can change the handle count at any time,
+    /// including potentially between calling this method and acting on the result.
+    #[must_use]
+    #[inline]
+    pub fn handle_count(this: &Self) -> usize {
+        this.0.handles.load(Ordering::Relaxed)
+    }
+
+    /// Gets the maximum number of `BoundedArc` handles to this allocation.
+    #[must_use]
+    #[inline]
+    pub fn max_handles(this: &Self) -> usize {
+        this.0.max
+    }
+
+    /// Returns `true` if the two `BoundedArc`s point to the same allocation
+    /// (in a vein similar to [`std::ptr::eq`]).
+    #[must_use]
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T: ?Sized> Drop for BoundedArc<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.handles.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T: ?Sized> Deref for BoundedArc<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0.data
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for BoundedArc<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0.data
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for BoundedArc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0.data, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for BoundedArc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0.data, f)
+    }
+}
+
+/// The error type returned by [`BoundedArc::try_clone`] when the maximum number of
+/// handles has been reached.
+#[derive(Debug, Copy, Clone)]
+pub struct HandleLimitError {
+    max: usize,
+}
+
+impl HandleLimitError {
+    /// Returns the maximum number of handles that was reached.
+    #[must_use]
+    #[inline]
+    pub const fn max(self) -> usize {
+        self.max
+    }
+}
+
+impl Error for HandleLimitError {}
+
+impl fmt::Display for HandleLimitError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "reached the maximum of {} handles", self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::BoundedArc;
+    use std::thread;
+
+    #[test]
+    fn limit_is_hit_at_max() {
+        let counter = DropCounter::new();
+        let a = BoundedArc::try_new(counter.wrap(1), 3).unwrap();
+        let b = BoundedArc::try_clone(&a).unwrap();
+        let c = BoundedArc::try_clone(&b).unwrap();
+        assert_eq!(BoundedArc::handle_count(&a), 3);
+        assert_eq!(BoundedArc::try_clone(&a).unwrap_err().max(), 3);
+        assert!(BoundedArc::ptr_eq(&a, &c));
+        drop((a, b, c));
+        assert_eq!(counter.live(), 0);
+    }
+
+    #[test]
+    fn dropping_a_handle_frees_a_permit() {
+        let a = BoundedArc::try_new(1, 2).unwrap();
+        let b = BoundedArc::try_clone(&a).unwrap();
+        assert!(BoundedArc::try_clone(&a).is_err());
+        drop(b);
+        assert_eq!(BoundedArc::handle_count(&a), 1);
+        let b = BoundedArc::try_clone(&a).unwrap();
+        assert_eq!(*b, 1);
+    }
+
+    #[test]
+    fn max_of_zero_behaves_like_one() {
+        let a = BoundedArc::try_new(1, 0).unwrap();
+        assert_eq!(BoundedArc::max_handles(&a), 1);
+        assert_eq!(BoundedArc::handle_count(&a), 1);
+        assert_eq!(BoundedArc::try_clone(&a).unwrap_err().max(), 1);
+    }
+
+    #[test]
+    fn concurrent_try_clone_never_exceeds_max() {
+        const MAX: usize = 4;
+        let a = BoundedArc::try_new((), MAX).unwrap();
+        thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(|| {
+                    for _ in 0..1000 {
+                        if let Ok(b) = BoundedArc::try_clone(&a) {
+                            assert!(BoundedArc::handle_count(&b) <= MAX);
+                            thread::yield_now();
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(BoundedArc::handle_count(&a), 1);
+    }
+}
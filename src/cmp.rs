@@ -0,0 +1,78 @@
+//! Comparisons between `Arc<str>`, `Arc<[T]>` and other string and slice types.
+
+use crate::Arc;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+macro_rules! impl_str_cmp {
+    ($rhs:ty $(, $lt:lifetime)?) => {
+        impl<$($lt)?> PartialEq<$rhs> for Arc<str> {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                PartialEq::eq(&self[..], &other[..])
+            }
+        }
+
+        impl<$($lt)?> PartialEq<Arc<str>> for $rhs {
+            #[inline]
+            fn eq(&self, other: &Arc<str>) -> bool {
+                PartialEq::eq(&self[..], &other[..])
+            }
+        }
+
+        impl<$($lt)?> PartialOrd<$rhs> for Arc<str> {
+            #[inline]
+            fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+                PartialOrd::partial_cmp(&self[..], &other[..])
+            }
+        }
+
+        impl<$($lt)?> PartialOrd<Arc<str>> for $rhs {
+            #[inline]
+            fn partial_cmp(&self, other: &Arc<str>) -> Option<Ordering> {
+                PartialOrd::partial_cmp(&self[..], &other[..])
+            }
+        }
+    };
+}
+
+impl_str_cmp!(str);
+impl_str_cmp!(&'a str, 'a);
+impl_str_cmp!(String);
+impl_str_cmp!(Cow<'a, str>, 'a);
+
+macro_rules! impl_slice_cmp {
+    ($rhs:ty $(, $lt:lifetime)?) => {
+        impl<$($lt,)? T: PartialEq> PartialEq<$rhs> for Arc<[T]> {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                PartialEq::eq(&self[..], &other[..])
+            }
+        }
+
+        impl<$($lt,)? T: PartialEq> PartialEq<Arc<[T]>> for $rhs {
+            #[inline]
+            fn eq(&self, other: &Arc<[T]>) -> bool {
+                PartialEq::eq(&self[..], &other[..])
+            }
+        }
+
+        impl<$($lt,)? T: PartialOrd> PartialOrd<$rhs> for Arc<[T]> {
+            #[inline]
+            fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+                PartialOrd::partial_cmp(&self[..], &other[..])
+            }
+        }
+
+        impl<$($lt,)? T: PartialOrd> PartialOrd<Arc<[T]>> for $rhs {
+            #[inline]
+            fn partial_cmp(&self, other: &Arc<[T]>) -> Option<Ordering> {
+                PartialOrd::partial_cmp(&self[..], &other[..])
+            }
+        }
+    };
+}
+
+impl_slice_cmp!([T]);
+impl_slice_cmp!(&'a [T], 'a);
+impl_slice_cmp!(Vec<T>);
@@ -0,0 +1,132 @@
+//! Merging of `Arc`s with equal values.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::mem;
+
+/// Statistics returned by [`dedup_arcs`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct DedupStats {
+    replaced: usize,
+    freed: usize,
+    freed_bytes: usize,
+}
+
+impl DedupStats {
+    /// Returns the number of `Arc`s that were replaced by an equal `Arc` pointing to
+    /// another allocation.
+    #[must_use]
+    #[inline]
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    /// Returns the number of inner values dropped because their last `Arc` was replaced.
+    #[must_use]
+    #[inline]
+    pub fn freed(&self) -> usize {
+        self.freed
+    }
+
+    /// Returns the total size of the inner values that were dropped.
+    ///
+    /// This is the size of the values themselves, and does not include the reference
+    /// counts or any memory owned by the values.
+    #[must_use]
+    #[inline]
+    pub fn freed_bytes(&self) -> usize {
+        self.freed_bytes
+    }
+}
+
+/// Makes all equal `Arc`s yielded by `arcs` point to a single allocation.
+///
+/// Each `Arc` is replaced by the first `Arc` with an equal value that the iterator
+/// yielded. Allocations that are no longer referenced are freed, unless they are kept
+/// alive by `Arc`s outside of `arcs`.
+///
+/// Returns an error if growing the set of distinct values fails, in which case the
+/// `Arc`s already visited stay merged.
+pub fn dedup_arcs<'a, T, I>(arcs: I) -> Result<DedupStats, AllocError>
+where
+    T: ?Sized + Hash + Eq + 'a,
+    I: IntoIterator<Item = &'a mut Arc<T>>,
+{
+    let mut stats = DedupStats::default();
+    let mut distinct = HashSet::<Arc<T>>::new();
+    for arc in arcs {
+        match distinct.get(arc) {
+            Some(first) => {
+                if Arc::ptr_eq(first, arc) {
+                    continue;
+                }
+                if Arc::strong_count(arc) == 1 {
+                    stats.freed += 1;
+                    stats.freed_bytes += mem::size_of_val::<T>(arc);
+                }
+                *arc = first.clone();
+                stats.replaced += 1;
+            }
+            None => {
+                distinct.try_reserve(1)?;
+                // Does not grow the set, the capacity was reserved above.
+                #[allow(clippy::disallowed_methods)]
+                distinct.insert(arc.clone());
+            }
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::{dedup_arcs, Arc};
+
+    #[test]
+    fn merges_equal_values() {
+        let counter = DropCounter::new();
+        let mut arcs: Vec<_> = [1, 2, 1, 1, 2, 3]
+            .into_iter()
+            .map(|v| Arc::try_new(counter.wrap(v)).unwrap())
+            .collect();
+        let stats = dedup_arcs(&mut arcs).unwrap();
+        assert_eq!(stats.replaced(), 3);
+        assert_eq!(stats.freed(), 3);
+        assert_eq!(stats.freed_bytes(), 3 * std::mem::size_of_val(&*arcs[0]));
+        assert_eq!(counter.live(), 3);
+        assert!(Arc::ptr_eq(&arcs[0], &arcs[2]));
+        assert!(Arc::ptr_eq(&arcs[0], &arcs[3]));
+        assert!(Arc::ptr_eq(&arcs[1], &arcs[4]));
+        assert_eq!(Arc::strong_count(&arcs[0]), 3);
+    }
+
+    #[test]
+    fn outside_clones_keep_their_allocation() {
+        let counter = DropCounter::new();
+        let mut arcs = [
+            Arc::try_new(counter.wrap(1)).unwrap(),
+            Arc::try_new(counter.wrap(1)).unwrap(),
+        ];
+        let outside = arcs[1].clone();
+        let stats = dedup_arcs(&mut arcs).unwrap();
+        assert_eq!(stats.replaced(), 1);
+        assert_eq!(stats.freed(), 0);
+        assert_eq!(stats.freed_bytes(), 0);
+        assert!(Arc::ptr_eq(&arcs[0], &arcs[1]));
+        assert!(!Arc::ptr_eq(&arcs[1], &outside));
+        assert_eq!(counter.live(), 2);
+        drop(outside);
+        assert_eq!(counter.live(), 1);
+    }
+
+    #[test]
+    fn shared_allocations_are_not_replaced() {
+        let a = Arc::try_new(1).unwrap();
+        let mut arcs = [a.clone(), a];
+        let stats = dedup_arcs(&mut arcs).unwrap();
+        assert_eq!(stats, Default::default());
+    }
+}
@@ -0,0 +1,288 @@
+//! Routing of drops to a designated thread.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::alloc::Layout;
+use std::fmt;
+use std::ops::Deref;
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::thread::{self, ThreadId};
+
+struct Header {
+    next: *mut Header,
+    drop: unsafe fn(*mut Header),
+}
+
+#[repr(C)]
+struct Node<T> {
+    header: Header,
+    value: T,
+}
+
+/// Drops the value and frees the node.
+///
+/// # Safety
+///
+/// `header` must point to the header of a live `Node<T>` allocated by `DropQueue::try_wrap`.
+unsafe fn drop_node<T>(header: *mut Header) {
+    drop(Box::from_raw(header.cast::<Node<T>>()));
+}
+
+struct Shared {
+    owner: ThreadId,
+    head: AtomicPtr<Header>,
+}
+
+impl Shared {
+    fn pump(&self) -> usize {
+        if thread::current_id() != self.owner {
+            return 0;
+        }
+        let mut count = 0;
+        let mut header = self.head.swap(ptr::null_mut(), Ordering::Acquire);
+        while !header.is_null() {
+            // SAFETY: nodes are pushed by `ThreadAffine::drop`, which gives up ownership
+            // of them, and `swap` above has taken them out of the queue.
+            unsafe {
+                let next = (*header).next;
+                ((*header).drop)(header);
+                header = next;
+            }
+            count += 1;
+        }
+        count
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        self.pump();
+    }
+}
+
+/// A queue of values that must be dropped on the thread that created the queue.
+///
+/// Values are wrapped with [`try_wrap`], usually to be shared as `Arc<ThreadAffine<T>>`.
+/// When the wrapper is dropped on the owner thread, the value is dropped right away.
+/// When it is dropped on any other thread, the value is queued instead, and is dropped
+/// by the next call to [`pump`] on the owner thread.
+///
+/// Queueing a value never allocates, because the storage for it is allocated when the
+/// value is wrapped. Dropping a `DropQueue` handle on the owner thread also pumps the
+/// queue.
+///
+/// The owner thread has to keep a `DropQueue` handle, and keep pumping it, until every
+/// wrapped value has been dropped. A value that is queued after the owner thread has
+/// dropped its last handle, or has exited, is leaked rather than dropped on the wrong
+/// thread.
+///
+/// [`try_wrap`]: DropQueue::try_wrap
+/// [`pump`]: DropQueue::pump
+#[derive(Clone)]
+pub struct DropQueue(Arc<Shared>);
+
+impl DropQueue {
+    /// Constructs a new `DropQueue` owned by the current thread, returning an error if
+    /// allocation fails.
+    #[inline]
+    pub fn try_new() -> Result<DropQueue, AllocError> {
+        Ok(DropQueue(Arc::try_new(Shared {
+            owner: thread::current_id(),
+            head: AtomicPtr::new(ptr::null_mut()),
+        })?))
+    }
+
+    /// Wraps `value` so that it is dropped on the owner thread of this queue, returning
+    /// an error if allocation fails.
+    pub fn try_wrap<T: Send>(&self, value: T) -> Result<ThreadAffine<T>, AllocError> {
+        let node = Box::try_new(Node {
+            header: Header {
+                next: ptr::null_mut(),
+                drop: drop_node::<T>,
+            },
+            value,
+        })
+        .map_err(|_| AllocError::new(Layout::new::<Node<T>>()))?;
+        Ok(ThreadAffine {
+            node: NonNull::from(Box::leak(node)),
+            queue: self.0.clone(),
+        })
+    }
+
+    /// Drops all queued values, returning how many were dropped.
+    ///
+    /// Does nothing and returns 0 if not called on the owner thread.
+    #[inline]
+    pub fn pump(&self) -> usize {
+        self.0.pump()
+    }
+
+    /// Returns `true` if the current thread is the owner thread of this queue.
+    #[must_use]
+    #[inline]
+    pub fn is_owner_thread(&self) -> bool {
+        thread::current_id() == self.0.owner
+    }
+}
+
+impl Drop for DropQueue {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.pump();
+    }
+}
+
+impl fmt::Debug for DropQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DropQueue")
+            .field("owner", &self.0.owner)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A value that is dropped on the owner thread of a [`DropQueue`].
+///
+/// See [`DropQueue::try_wrap`].
+pub struct ThreadAffine<T> {
+    node: NonNull<Node<T>>,
+    queue: Arc<Shared>,
+}
+
+// SAFETY: `ThreadAffine<T>` owns a `T` just like a `Box<T>` does.
+unsafe impl<T: Send> Send for ThreadAffine<T> {}
+// SAFETY: `ThreadAffine<T>` only hands out `&T` through a shared reference.
+unsafe impl<T: Sync> Sync for ThreadAffine<T> {}
+
+impl<T> Drop for ThreadAffine<T> {
+    fn drop(&mut self) {
+        let header = self.node.as_ptr().cast::<Header>();
+        if thread::current_id() == self.queue.owner {
+            // SAFETY: the node is owned by `self` and is not referenced anywhere else.
+            unsafe { drop_node::<T>(header) };
+            return;
+        }
+        let head = &self.queue.head;
+        let mut next = head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the node is owned by `self` until it is published by the exchange below.
+            unsafe { (*header).next = next };
+            match head.compare_exchange_weak(next, header, Ordering::Release, Ordering::Relaxed) {
+                Ok(_) => return,
+                Err(actual) => next = actual,
+            }
+        }
+    }
+}
+
+impl<T> Deref for ThreadAffine<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: the node is live for as long as `self` is.
+        unsafe { &self.node.as_ref().value }
+    }
+}
+
+impl<T> AsRef<T> for ThreadAffine<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ThreadAffine<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ThreadAffine<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::{Arc, DropQueue};
+    use std::thread;
+
+    #[test]
+    fn drops_on_owner_thread_right_away() {
+        let counter = DropCounter::new();
+        let queue = DropQueue::try_new().unwrap();
+        let value = queue.try_wrap(counter.wrap(())).unwrap();
+        drop(value);
+        assert_eq!(counter.dropped(), 1);
+        assert_eq!(queue.pump(), 0);
+    }
+
+    #[test]
+    fn queues_drops_from_other_threads() {
+        static COUNTER: DropCounter = DropCounter::new();
+        let queue = DropQueue::try_new().unwrap();
+        let value = Arc::try_new(queue.try_wrap(COUNTER.wrap(())).unwrap()).unwrap();
+        thread::spawn(move || drop(value)).join().unwrap();
+        assert_eq!(COUNTER.dropped(), 0);
+        assert_eq!(queue.pump(), 1);
+        assert_eq!(COUNTER.dropped(), 1);
+        assert_eq!(queue.pump(), 0);
+    }
+
+    #[test]
+    fn pump_does_nothing_on_other_threads() {
+        static COUNTER: DropCounter = DropCounter::new();
+        let queue = DropQueue::try_new().unwrap();
+        let value = queue.try_wrap(COUNTER.wrap(())).unwrap();
+        let other = queue.clone();
+        thread::spawn(move || {
+            drop(value);
+            assert!(!other.is_owner_thread());
+            assert_eq!(other.pump(), 0);
+        })
+        .join()
+        .unwrap();
+        assert_eq!(COUNTER.dropped(), 0);
+        assert_eq!(queue.pump(), 1);
+    }
+
+    #[test]
+    fn concurrent_pushes() {
+        static COUNTER: DropCounter = DropCounter::new();
+        const THREADS: usize = 8;
+        const VALUES: usize = 100;
+        let queue = DropQueue::try_new().unwrap();
+        let mut handles = Vec::new();
+        for _ in 0..THREADS {
+            let mut values = Vec::new();
+            for i in 0..VALUES {
+                values.push(queue.try_wrap(COUNTER.wrap(i)).unwrap());
+            }
+            handles.push(thread::spawn(move || drop(values)));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(COUNTER.dropped(), 0);
+        assert_eq!(queue.pump(), THREADS * VALUES);
+        assert_eq!(COUNTER.dropped(), THREADS * VALUES);
+    }
+
+    #[test]
+    fn dropping_last_handle_pumps() {
+        static COUNTER: DropCounter = DropCounter::new();
+        let queue = DropQueue::try_new().unwrap();
+        let value = queue.try_wrap(COUNTER.wrap(())).unwrap();
+        let other = queue.try_wrap(COUNTER.wrap(())).unwrap();
+        thread::spawn(move || drop(value)).join().unwrap();
+        drop(queue);
+        assert_eq!(COUNTER.dropped(), 1);
+        drop(other);
+        assert_eq!(COUNTER.dropped(), 2);
+    }
+}
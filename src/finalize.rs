@@ -0,0 +1,137 @@
+//! Asynchronous cleanup when the last `Arc` is dropped.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::alloc::Layout;
+use std::fmt;
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::sync::Arc as StdArc;
+
+type Finalizer<T> = Box<dyn FnOnce(T) + Send>;
+
+/// A value with a finalizer that takes ownership of it when it is dropped.
+///
+/// This is created by [`Arc::try_new_with_async_finalizer`].
+pub struct Finalized<T> {
+    value: ManuallyDrop<T>,
+    finalizer: ManuallyDrop<Finalizer<T>>,
+}
+
+// SAFETY: the finalizer is only accessed through `&mut self` in `drop`, a shared
+// `&Finalized<T>` only gives access to the value.
+unsafe impl<T: Sync> Sync for Finalized<T> {}
+
+impl<T> Arc<Finalized<T>> {
+    /// Constructs a new `Arc<Finalized<T>>` that hands the value over to an async
+    /// cleanup when the last `Arc` is dropped, returning an error if allocation fails.
+    ///
+    /// When the value is dropped, `finalizer` is called with it and the returned future
+    /// is passed to `spawner`, for example `|fut| { tokio::spawn(fut); }`. The dropping
+    /// thread only creates and spawns the future, it never blocks on the cleanup.
+    ///
+    /// If allocation fails, `value` is dropped normally and `finalizer` is never called.
+    pub fn try_new_with_async_finalizer<F, Fut, S>(value: T, finalizer: F, spawner: S) -> Result<Self, AllocError>
+    where
+        F: FnOnce(T) -> Fut + Send + 'static,
+        Fut: Future<Output = ()>,
+        S: FnOnce(Fut) + Send + 'static,
+    {
+        // The `Arc` is allocated first: once `Finalized` exists, dropping it runs the
+        // finalizer.
+        let mut arc =
+            StdArc::<Finalized<T>>::try_new_uninit().map_err(|_| AllocError::new(Layout::new::<Finalized<T>>()))?;
+        let finalizer = move |value| spawner(finalizer(value));
+        let layout = Layout::for_value(&finalizer);
+        let finalizer: Finalizer<T> = Box::try_new(finalizer).map_err(|_| AllocError::new(layout))?;
+        // SAFETY: `arc` has just been allocated, so it is the only pointer to it.
+        unsafe {
+            StdArc::get_mut_unchecked(&mut arc).write(Finalized {
+                value: ManuallyDrop::new(value),
+                finalizer: ManuallyDrop::new(finalizer),
+            })
+        };
+        // SAFETY: the value has just been initialized.
+        Ok(Arc::from_std(unsafe { StdArc::from_raw(StdArc::into_raw(arc).cast()) }))
+    }
+}
+
+impl<T> Drop for Finalized<T> {
+    fn drop(&mut self) {
+        // SAFETY: both fields are taken exactly once, here.
+        let (value, finalizer) = unsafe {
+            (
+                ManuallyDrop::take(&mut self.value),
+                ManuallyDrop::take(&mut self.finalizer),
+            )
+        };
+        finalizer(value);
+    }
+}
+
+impl<T> Deref for Finalized<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> AsRef<T> for Finalized<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Finalized<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.value, f)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Finalized<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.value, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::Arc;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc as StdArc, Mutex};
+
+    type Spawned = StdArc<Mutex<Vec<Pin<Box<dyn Future<Output = ()> + Send>>>>>;
+
+    static COUNTER: DropCounter = DropCounter::new();
+
+    #[test]
+    fn finalizer_runs_once_on_last_drop() {
+        let spawned = Spawned::default();
+        let queue = spawned.clone();
+        let arc = Arc::try_new_with_async_finalizer(
+            COUNTER.wrap(7),
+            |value| async move { assert_eq!(*value, 7) },
+            move |fut| queue.lock().unwrap().push(Box::pin(fut)),
+        )
+        .unwrap();
+        assert_eq!(***arc, 7);
+
+        let clone = arc.clone();
+        drop(arc);
+        assert!(spawned.lock().unwrap().is_empty());
+        drop(clone);
+        assert_eq!(spawned.lock().unwrap().len(), 1);
+        // The value is owned by the spawned future until it runs.
+        assert_eq!(COUNTER.live(), 1);
+        spawned.lock().unwrap().clear();
+        assert_eq!(COUNTER.live(), 0);
+    }
+}
@@ -0,0 +1,68 @@
+//! Abstraction over the kind of shared pointer.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::ops::Deref;
+
+/// A kind of reference-counted pointer, such as [`ArcK`] for [`Arc`].
+///
+/// Data structures that are generic over `P: SharedPointerKind` can store their shared
+/// parts as [`Shared<T, P>`] and leave the choice of pointer to their users.
+pub trait SharedPointerKind {
+    /// The pointer type of this kind.
+    type Pointer<T: ?Sized>: Clone + Deref<Target = T>;
+
+    /// Constructs a new pointer, returning an error if allocation fails.
+    fn try_new<T>(data: T) -> Result<Self::Pointer<T>, AllocError>;
+
+    /// Returns `true` if the two pointers point to the same allocation.
+    fn ptr_eq<T: ?Sized>(this: &Self::Pointer<T>, other: &Self::Pointer<T>) -> bool;
+
+    /// Returns a mutable reference into the given pointer, if there are no other
+    /// pointers to the same allocation.
+    fn get_mut<T: ?Sized>(this: &mut Self::Pointer<T>) -> Option<&mut T>;
+
+    /// Makes a mutable reference into the given pointer, calling `f` to produce a copy
+    /// of the inner value in a new allocation if it is shared.
+    ///
+    /// Returns an error if `f` fails or if allocating the new pointer fails.
+    fn make_mut_with<T, F, E>(this: &mut Self::Pointer<T>, f: F) -> Result<&mut T, E>
+    where
+        F: FnOnce(&T) -> Result<T, E>,
+        E: From<AllocError>;
+}
+
+/// The [`SharedPointerKind`] of [`Arc`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ArcK;
+
+impl SharedPointerKind for ArcK {
+    type Pointer<T: ?Sized> = Arc<T>;
+
+    #[inline]
+    fn try_new<T>(data: T) -> Result<Arc<T>, AllocError> {
+        Arc::try_new(data)
+    }
+
+    #[inline]
+    fn ptr_eq<T: ?Sized>(this: &Arc<T>, other: &Arc<T>) -> bool {
+        Arc::ptr_eq(this, other)
+    }
+
+    #[inline]
+    fn get_mut<T: ?Sized>(this: &mut Arc<T>) -> Option<&mut T> {
+        Arc::get_mut(this)
+    }
+
+    #[inline]
+    fn make_mut_with<T, F, E>(this: &mut Arc<T>, f: F) -> Result<&mut T, E>
+    where
+        F: FnOnce(&T) -> Result<T, E>,
+        E: From<AllocError>,
+    {
+        Arc::make_mut_with(this, f)
+    }
+}
+
+/// The pointer to a `T` of the [`SharedPointerKind`] `P`.
+pub type Shared<T, P> = <P as SharedPointerKind>::Pointer<T>;
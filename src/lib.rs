@@ -1,11 +1,65 @@
 //! A library for fallible Arc.
+//!
+//! Allocation failure is reported as an [`AllocError`] instead of aborting. The aborting
+//! std allocation APIs listed in the crate's `clippy.toml` are rejected in CI, but that
+//! list is not exhaustive, and the crate is built on `std`, so it does not build against
+//! a liballoc with `no_global_oom_handling`.
 
 #![feature(allocator_api)]
+#![feature(clone_from_ref)]
+#![feature(clone_to_uninit)]
+#![feature(coerce_unsized)]
+#![feature(current_thread_id)]
+#![feature(dispatch_from_dyn)]
+#![feature(get_mut_unchecked)]
+#![feature(layout_for_ptr)]
+#![feature(unsize)]
+#![feature(vec_push_within_capacity)]
+// Tests may allocate infallibly, see `clippy.toml`.
+#![cfg_attr(test, allow(clippy::disallowed_methods, clippy::disallowed_macros))]
 
 mod arc;
+mod borrowed;
+mod bounded;
+mod cmp;
+mod dedup;
+mod drop_queue;
+mod finalize;
+mod kind;
+mod observer;
+mod pin_weak;
+mod projected;
+mod revocable;
+mod slice;
+mod subslice;
+mod substr;
+mod type_map;
 mod weak;
 
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std-compat")]
+pub mod std_compat;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+#[cfg(feature = "watermark")]
+pub mod watermark;
+
 pub use arc::Arc;
-pub use weak::Weak;
+pub use borrowed::BorrowedArc;
+pub use bounded::{BoundedArc, HandleLimitError};
+pub use dedup::{dedup_arcs, DedupStats};
+pub use drop_queue::{DropQueue, ThreadAffine};
+pub use finalize::Finalized;
+pub use kind::{ArcK, Shared, SharedPointerKind};
+pub use observer::{Observer, ObserverList};
+pub use pin_weak::PinWeak;
+pub use projected::ProjectedArc;
+pub use revocable::{Revocable, RevocableGuard, RevocableHandle};
+pub use slice::IntoOwnedError;
+pub use subslice::{ChunksOwned, SubsliceArc};
+pub use substr::SubstrArc;
+pub use type_map::ArcTypeMap;
+pub use weak::{UpgradeLive, Weak};
 
 pub use fallacy_alloc::AllocError;
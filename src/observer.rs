@@ -0,0 +1,196 @@
+//! Event dispatch to weakly held subscribers.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::fmt;
+use std::sync::Arc as StdArc;
+use std::sync::Weak as StdWeak;
+use std::sync::{Mutex, PoisonError};
+
+/// A subscriber of an [`ObserverList`].
+///
+/// This is implemented for all closures taking a reference to the event.
+pub trait Observer<E: ?Sized>: Send + Sync {
+    /// Handles an event.
+    fn notify(&self, event: &E);
+}
+
+impl<E: ?Sized, F> Observer<E> for F
+where
+    F: Fn(&E) + Send + Sync,
+{
+    #[inline]
+    fn notify(&self, event: &E) {
+        self(event)
+    }
+}
+
+/// A list of subscribers that are held by [`Weak`](crate::Weak) references.
+///
+/// Subscribing does not keep an observer alive: once all [`Arc`]s to it are dropped,
+/// it is no longer notified and is pruned from the list automatically.
+pub struct ObserverList<E: ?Sized> {
+    observers: Mutex<Vec<StdWeak<dyn Observer<E>>>>,
+}
+
+impl<E: ?Sized> ObserverList<E> {
+    /// Creates an empty `ObserverList`, without allocating any memory.
+    #[must_use]
+    #[inline]
+    pub fn new() -> ObserverList<E> {
+        ObserverList {
+            observers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Subscribes `observer` to the events of this list, returning an error if growing
+    /// the list fails.
+    pub fn try_subscribe<O>(&self, observer: &Arc<O>) -> Result<(), AllocError>
+    where
+        O: Observer<E> + 'static,
+    {
+        let observer: StdWeak<dyn Observer<E>> = Arc::downgrade(observer).into_std();
+        let mut observers = self.observers.lock().unwrap_or_else(PoisonError::into_inner);
+        observers.retain(|o| o.strong_count() > 0);
+        observers.try_reserve(1)?;
+        // Cannot fail, the capacity was reserved above.
+        let _ = observers.push_within_capacity(observer);
+        Ok(())
+    }
+
+    /// Unsubscribes `observer` from the events of this list.
+    ///
+    /// Returns `true` if `observer` was subscribed.
+    pub fn unsubscribe<O>(&self, observer: &Arc<O>) -> bool
+    where
+        O: Observer<E> + 'static,
+    {
+        let ptr = Arc::as_ptr(observer).cast::<()>();
+        let mut observers = self.observers.lock().unwrap_or_else(PoisonError::into_inner);
+        match observers.iter().position(|o| o.as_ptr().cast::<()>() == ptr) {
+            Some(index) => {
+                observers.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Notifies all live observers of `event`, in the order they subscribed, and prunes
+    /// the observers that have been dropped.
+    ///
+    /// Observers are called without holding the internal lock, so they may subscribe to
+    /// or unsubscribe from this list. Returns the number of observers notified, or an
+    /// error if allocating the snapshot of live observers fails.
+    pub fn notify(&self, event: &E) -> Result<usize, AllocError> {
+        let live = {
+            let mut observers = self.observers.lock().unwrap_or_else(PoisonError::into_inner);
+            observers.retain(|o| o.strong_count() > 0);
+            let mut live = Vec::<StdArc<dyn Observer<E>>>::new();
+            live.try_reserve_exact(observers.len())?;
+            for observer in observers.iter().filter_map(StdWeak::upgrade) {
+                // Cannot fail, the capacity was reserved above.
+                let _ = live.push_within_capacity(observer);
+            }
+            live
+        };
+        for observer in &live {
+            observer.notify(event);
+        }
+        Ok(live.len())
+    }
+
+    /// Returns the number of observers that are still alive.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let observers = self.observers.lock().unwrap_or_else(PoisonError::into_inner);
+        observers.iter().filter(|o| o.strong_count() > 0).count()
+    }
+
+    /// Returns `true` if there are no live observers.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<E: ?Sized> Default for ObserverList<E> {
+    #[inline]
+    fn default() -> ObserverList<E> {
+        ObserverList::new()
+    }
+}
+
+impl<E: ?Sized> fmt::Debug for ObserverList<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObserverList").field("len", &self.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, ObserverList};
+    use std::sync::{Arc as StdArc, Mutex};
+
+    type Log = StdArc<Mutex<Vec<(usize, u32)>>>;
+
+    fn logger(log: &Log, id: usize) -> Arc<impl Fn(&u32) + Send + Sync> {
+        let log = log.clone();
+        Arc::try_new(move |event: &u32| log.lock().unwrap().push((id, *event))).unwrap()
+    }
+
+    #[test]
+    fn notifies_in_subscription_order() {
+        let log = Log::default();
+        let list = ObserverList::new();
+        let observers = [logger(&log, 0), logger(&log, 1), logger(&log, 2)];
+        for observer in [&observers[2], &observers[0], &observers[1]] {
+            list.try_subscribe(observer).unwrap();
+        }
+        assert_eq!(list.notify(&7).unwrap(), 3);
+        assert_eq!(*log.lock().unwrap(), [(2, 7), (0, 7), (1, 7)]);
+    }
+
+    #[test]
+    fn dead_observers_are_pruned() {
+        let log = Log::default();
+        let list = ObserverList::new();
+        let a = logger(&log, 0);
+        let b = logger(&log, 1);
+        list.try_subscribe(&a).unwrap();
+        list.try_subscribe(&b).unwrap();
+        assert_eq!(list.len(), 2);
+        drop(a);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.notify(&1).unwrap(), 1);
+        assert_eq!(*log.lock().unwrap(), [(1, 1)]);
+        drop(b);
+        assert!(list.is_empty());
+        assert_eq!(list.notify(&2).unwrap(), 0);
+    }
+
+    #[test]
+    fn observers_can_change_the_list_while_notified() {
+        let log = Log::default();
+        let list = Arc::try_new(ObserverList::<u32>::new()).unwrap();
+        let removed = logger(&log, 1);
+        let added = logger(&log, 2);
+        let changer = {
+            let (list, removed, added) = (list.clone(), removed.clone(), added.clone());
+            Arc::try_new(move |_: &u32| {
+                assert!(list.unsubscribe(&removed));
+                list.try_subscribe(&added).unwrap();
+            })
+            .unwrap()
+        };
+        list.try_subscribe(&changer).unwrap();
+        list.try_subscribe(&removed).unwrap();
+        // `removed` was in the snapshot, so it is still notified this time.
+        assert_eq!(list.notify(&1).unwrap(), 2);
+        assert!(!list.unsubscribe(&removed));
+        assert!(list.unsubscribe(&changer));
+        assert_eq!(list.notify(&2).unwrap(), 1);
+        assert_eq!(*log.lock().unwrap(), [(1, 1), (2, 2)]);
+    }
+}
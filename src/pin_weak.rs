@@ -0,0 +1,126 @@
+//! Weak pointers to pinned values.
+
+use crate::{Arc, Weak};
+use std::fmt;
+use std::pin::Pin;
+
+/// A [`Weak`] pointer to the value of a `Pin<Arc<T>>`, which upgrades to a pinned
+/// [`Arc`].
+///
+/// A `PinWeak` can only be created from a pinned `Arc`, so the value it upgrades to is
+/// known to be pinned.
+#[repr(transparent)]
+pub struct PinWeak<T: ?Sized>(Weak<T>);
+
+impl<T> PinWeak<T> {
+    /// Constructs a new `PinWeak<T>`, without allocating any memory.
+    /// Calling [`upgrade`] on the return value always gives [`None`].
+    ///
+    /// [`upgrade`]: PinWeak::upgrade
+    #[must_use]
+    #[inline]
+    pub fn new() -> PinWeak<T> {
+        PinWeak(Weak::new())
+    }
+}
+
+impl<T: ?Sized> PinWeak<T> {
+    /// Creates a new `PinWeak` pointer to the allocation of the pinned `Arc`.
+    #[must_use]
+    #[inline]
+    pub fn downgrade(this: &Pin<Arc<T>>) -> PinWeak<T> {
+        // SAFETY: `Pin` is `repr(transparent)`, and the `Arc` is only used to create a
+        // `Weak`, which can't reach the value without upgrading back to a pinned `Arc`.
+        let arc = unsafe { &*(this as *const Pin<Arc<T>>).cast::<Arc<T>>() };
+        PinWeak(Arc::downgrade(arc))
+    }
+
+    /// Attempts to upgrade the `PinWeak` pointer to a pinned [`Arc`], delaying
+    /// dropping of the inner value if successful.
+    ///
+    /// Returns [`None`] if the inner value has since been dropped.
+    #[must_use = "this returns a new `Pin<Arc>`, \
+                  without modifying the original weak pointer"]
+    #[inline]
+    pub fn upgrade(&self) -> Option<Pin<Arc<T>>> {
+        // SAFETY: the allocation was pinned when this `PinWeak` was created, and stays
+        // pinned until its value is dropped.
+        self.0.upgrade().map(|arc| unsafe { Pin::new_unchecked(arc) })
+    }
+
+    /// Gets the number of strong (`Pin<Arc>`) pointers pointing to this allocation.
+    ///
+    /// If `self` was created using [`PinWeak::new`], this will return 0.
+    #[must_use]
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    /// Gets an approximation of the number of `PinWeak` pointers pointing to this
+    /// allocation.
+    ///
+    /// See [`Weak::weak_count`].
+    #[must_use]
+    #[inline]
+    pub fn weak_count(&self) -> usize {
+        self.0.weak_count()
+    }
+
+    /// Returns `true` if the two `PinWeak`s point to the same allocation, or if both
+    /// don't point to any allocation.
+    #[inline]
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+}
+
+impl<T: ?Sized> Clone for PinWeak<T> {
+    /// Makes a clone of the `PinWeak` pointer that points to the same allocation.
+    #[inline]
+    fn clone(&self) -> PinWeak<T> {
+        PinWeak(self.0.clone())
+    }
+}
+
+impl<T> Default for PinWeak<T> {
+    /// Constructs a new `PinWeak<T>`, without allocating any memory.
+    #[inline]
+    fn default() -> PinWeak<T> {
+        PinWeak::new()
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for PinWeak<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::{Arc, PinWeak};
+
+    #[test]
+    fn downgrade_upgrade_and_expiry() {
+        let counter = DropCounter::new();
+        let pinned = Arc::try_pin(counter.wrap(5)).unwrap();
+        let weak = PinWeak::downgrade(&pinned);
+        assert_eq!(weak.strong_count(), 1);
+        assert_eq!(weak.weak_count(), 1);
+
+        let upgraded = weak.upgrade().unwrap();
+        assert_eq!(**upgraded, 5);
+        assert_eq!(weak.strong_count(), 2);
+        assert!(weak.ptr_eq(&PinWeak::downgrade(&upgraded)));
+        drop((pinned, upgraded));
+
+        assert_eq!(counter.live(), 0);
+        assert_eq!(weak.strong_count(), 0);
+        assert!(weak.upgrade().is_none());
+        assert!(PinWeak::<u8>::new().upgrade().is_none());
+    }
+}
@@ -0,0 +1,171 @@
+//! Owned references to a part of the value in an `Arc`.
+
+use crate::Arc;
+use std::fmt;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+/// An owned reference to a part of the value in an [`Arc<T>`](Arc), such as one of
+/// its fields.
+///
+/// A `ProjectedArc` keeps the whole allocation alive and derefs to the projected part.
+/// It is created with [`Arc::project`], or with [`Arc::project_pin`] for pinned fields
+/// of a `Pin<Arc<T>>`.
+pub struct ProjectedArc<T: ?Sized, U: ?Sized> {
+    arc: Arc<T>,
+    field: NonNull<U>,
+}
+
+// SAFETY: `ProjectedArc` owns an `Arc<T>` and only hands out `&U`.
+unsafe impl<T: ?Sized + Send + Sync, U: ?Sized + Sync> Send for ProjectedArc<T, U> {}
+// SAFETY: `ProjectedArc` owns an `Arc<T>` and only hands out `&U`.
+unsafe impl<T: ?Sized + Send + Sync, U: ?Sized + Sync> Sync for ProjectedArc<T, U> {}
+
+impl<T: ?Sized> Arc<T> {
+    /// Creates a [`ProjectedArc`] referring to the part of the inner value returned by `f`.
+    #[inline]
+    pub fn project<U: ?Sized, F>(this: Self, f: F) -> ProjectedArc<T, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let field = NonNull::from(f(&this));
+        ProjectedArc { arc: this, field }
+    }
+
+    /// Creates a pinned [`ProjectedArc`] referring to the part of the pinned inner value
+    /// returned by `f`.
+    ///
+    /// `f` has to project `Pin<&T>` to `Pin<&U>` itself, for example with a projection
+    /// generated by `pin-project`, or with [`Pin::map_unchecked`] for fields that are
+    /// structurally pinned.
+    #[inline]
+    pub fn project_pin<U: ?Sized, F>(this: Pin<Self>, f: F) -> Pin<ProjectedArc<T, U>>
+    where
+        F: FnOnce(Pin<&T>) -> Pin<&U>,
+    {
+        // SAFETY: the `Arc` is only used to keep the allocation alive, its value is never
+        // moved or exposed unpinned.
+        let this = unsafe { Pin::into_inner_unchecked(this) };
+        // SAFETY: the inner value is pinned, since `this` was.
+        let field = NonNull::from(Pin::get_ref(f(unsafe { Pin::new_unchecked(&*this) })));
+        // SAFETY: the field is pinned and lives in the allocation that `this` keeps alive,
+        // and `ProjectedArc` never hands out `&mut U`.
+        unsafe { Pin::new_unchecked(ProjectedArc { arc: this, field }) }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> ProjectedArc<T, U> {
+    /// Projects further, to the part of the projected value returned by `f`.
+    #[inline]
+    pub fn map<V: ?Sized, F>(this: Self, f: F) -> ProjectedArc<T, V>
+    where
+        F: FnOnce(&U) -> &V,
+    {
+        let field = NonNull::from(f(&this));
+        ProjectedArc { arc: this.arc, field }
+    }
+
+    /// Projects further, to the part of the pinned projected value returned by `f`.
+    ///
+    /// See [`Arc::project_pin`].
+    #[inline]
+    pub fn map_pin<V: ?Sized, F>(this: Pin<Self>, f: F) -> Pin<ProjectedArc<T, V>>
+    where
+        F: FnOnce(Pin<&U>) -> Pin<&V>,
+    {
+        // SAFETY: the `Arc` is only used to keep the allocation alive, as in
+        // `Arc::project_pin`.
+        let this = unsafe { Pin::into_inner_unchecked(this) };
+        // SAFETY: the projected value is pinned, since `this` was.
+        let field = NonNull::from(Pin::get_ref(f(unsafe { Pin::new_unchecked(&*this) })));
+        // SAFETY: as in `Arc::project_pin`.
+        unsafe { Pin::new_unchecked(ProjectedArc { arc: this.arc, field }) }
+    }
+
+    /// Returns `true` if the two `ProjectedArc`s point to the same part of the same
+    /// allocation.
+    #[must_use]
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.arc, &other.arc) && this.field.cast::<u8>() == other.field.cast::<u8>()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for ProjectedArc<T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        // SAFETY: the field was borrowed from the inner value, which `self.arc` keeps alive.
+        unsafe { self.field.as_ref() }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Clone for ProjectedArc<T, U> {
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    fn clone(&self) -> Self {
+        ProjectedArc {
+            arc: self.arc.clone(),
+            field: self.field,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Display> fmt::Display for ProjectedArc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Debug> fmt::Debug for ProjectedArc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{CountingDrop, DropCounter};
+    use crate::{Arc, ProjectedArc};
+    use std::marker::PhantomPinned;
+    use std::pin::Pin;
+
+    struct Outer<'a> {
+        inner: Inner<'a>,
+        _pinned: PhantomPinned,
+    }
+
+    struct Inner<'a> {
+        value: CountingDrop<'a, u32>,
+    }
+
+    #[test]
+    fn pinned_projection_keeps_the_allocation_alive() {
+        let counter = DropCounter::new();
+        let outer = Arc::try_pin(Outer {
+            inner: Inner { value: counter.wrap(3) },
+            _pinned: PhantomPinned,
+        })
+        .unwrap();
+        let address = &outer.inner.value as *const CountingDrop<'_, u32>;
+
+        // SAFETY: the fields are structurally pinned.
+        let inner = Arc::project_pin(outer.clone(), |outer| unsafe { outer.map_unchecked(|o| &o.inner) });
+        // SAFETY: as above.
+        let value = ProjectedArc::map_pin(inner, |inner| unsafe { inner.map_unchecked(|i| &i.value) });
+        drop(outer);
+
+        assert_eq!(**value, 3);
+        assert!(std::ptr::eq(&*value, address));
+        assert_eq!(counter.live(), 1);
+        let clone = Pin::clone(&value);
+        drop(value);
+        assert_eq!(counter.live(), 1);
+        drop(clone);
+        assert_eq!(counter.live(), 0);
+    }
+}
@@ -0,0 +1,196 @@
+//! A shared handle whose value can be revoked.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{PoisonError, RwLock};
+
+/// The owner of a value that can be revoked while [`RevocableHandle`]s to it are shared.
+///
+/// Holders access the value through [`RevocableHandle::access`], which returns a
+/// [`RevocableGuard`] as long as the value has not been revoked. After the owner calls
+/// [`revoke`], all future accesses fail even though handles still exist, and the value
+/// is dropped as soon as the outstanding guards are dropped.
+///
+/// Only the owner can revoke, so `Revocable` is not [`Clone`]; use [`handle`] to get
+/// clonable handles that can only access the value. Dropping the owner does not revoke
+/// the value.
+///
+/// [`revoke`]: Revocable::revoke
+/// [`handle`]: Revocable::handle
+pub struct Revocable<T: ?Sized>(RevocableHandle<T>);
+
+impl<T> Revocable<T> {
+    /// Constructs a new `Revocable<T>`, returning an error if allocation fails.
+    #[inline]
+    pub fn try_new(data: T) -> Result<Revocable<T>, AllocError> {
+        Revocable::try_from_arc(Arc::try_new(data)?)
+    }
+}
+
+impl<T: ?Sized> Revocable<T> {
+    /// Constructs a new `Revocable<T>` from an existing [`Arc`], returning an error if
+    /// allocation fails.
+    ///
+    /// Other clones of `data` are not affected by revocation.
+    #[inline]
+    pub fn try_from_arc(data: Arc<T>) -> Result<Revocable<T>, AllocError> {
+        Ok(Revocable(RevocableHandle(Arc::try_new(RwLock::new(Some(data)))?)))
+    }
+
+    /// Returns a new handle that can access the value until it is revoked.
+    #[must_use]
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    pub fn handle(&self) -> RevocableHandle<T> {
+        self.0.clone()
+    }
+
+    /// Returns a guard to the value, or [`None`] if it has been revoked.
+    #[must_use]
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    pub fn access(&self) -> Option<RevocableGuard<T>> {
+        self.0.access()
+    }
+
+    /// Revokes the value, so that all future calls to [`RevocableHandle::access`] return
+    /// [`None`].
+    ///
+    /// The value is dropped immediately if there are no outstanding guards, otherwise
+    /// it is dropped together with the last guard.
+    ///
+    /// Returns `true` if this call revoked the value, or `false` if it had already been
+    /// revoked.
+    pub fn revoke(&self) -> bool {
+        let data = (self.0).0.write().unwrap_or_else(PoisonError::into_inner).take();
+        data.is_some()
+    }
+
+    /// Returns `true` if the value has been revoked.
+    #[must_use]
+    #[inline]
+    pub fn is_revoked(&self) -> bool {
+        self.0.is_revoked()
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Revocable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.access() {
+            Some(guard) => f.debug_tuple("Revocable").field(&&*guard).finish(),
+            None => f.write_str("Revocable(<revoked>)"),
+        }
+    }
+}
+
+/// A clonable handle to a value owned by a [`Revocable`].
+///
+/// This struct is created by [`Revocable::handle`].
+pub struct RevocableHandle<T: ?Sized>(Arc<RwLock<Option<Arc<T>>>>);
+
+impl<T: ?Sized> RevocableHandle<T> {
+    /// Returns a guard to the value, or [`None`] if it has been revoked.
+    #[must_use]
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    pub fn access(&self) -> Option<RevocableGuard<T>> {
+        let slot = self.0.read().unwrap_or_else(PoisonError::into_inner);
+        // Not `Option::clone`, so that `track_caller` reaches `Arc::clone`.
+        let data = slot.as_ref()?;
+        Some(RevocableGuard(data.clone()))
+    }
+
+    /// Returns `true` if the value has been revoked.
+    #[must_use]
+    #[inline]
+    pub fn is_revoked(&self) -> bool {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).is_none()
+    }
+
+    /// Returns `true` if the two handles belong to the same [`Revocable`]
+    /// (in a vein similar to [`std::ptr::eq`]).
+    #[must_use]
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T: ?Sized> Clone for RevocableHandle<T> {
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    fn clone(&self) -> RevocableHandle<T> {
+        RevocableHandle(self.0.clone())
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RevocableHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.access() {
+            Some(guard) => f.debug_tuple("RevocableHandle").field(&&*guard).finish(),
+            None => f.write_str("RevocableHandle(<revoked>)"),
+        }
+    }
+}
+
+/// A guard returned by [`RevocableHandle::access`] that keeps the value alive.
+pub struct RevocableGuard<T: ?Sized>(Arc<T>);
+
+impl<T: ?Sized> Deref for RevocableGuard<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for RevocableGuard<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RevocableGuard<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::{Revocable, RevocableHandle};
+
+    #[test]
+    fn handles_lose_access_after_revoke() {
+        let counter = DropCounter::new();
+        let owner = Revocable::try_new(counter.wrap(5)).unwrap();
+        let handle = owner.handle();
+        let other = handle.clone();
+        assert!(RevocableHandle::ptr_eq(&handle, &other));
+
+        let guard = handle.access().unwrap();
+        assert_eq!(**guard, 5);
+        assert!(owner.revoke());
+        assert!(!owner.revoke());
+        assert!(other.is_revoked());
+        assert!(other.access().is_none());
+        assert_eq!(counter.live(), 1);
+
+        drop(guard);
+        assert_eq!(counter.live(), 0);
+    }
+
+    #[test]
+    fn dropping_owner_does_not_revoke() {
+        let owner = Revocable::try_new(1).unwrap();
+        let handle = owner.handle();
+        drop(owner);
+        assert_eq!(handle.access().as_deref(), Some(&1));
+    }
+}
@@ -0,0 +1,70 @@
+//! Helper modules for serializing [`Weak`] fields with `#[serde(with = "...")]`.
+//!
+//! A deserialized `Weak` can never point at a live allocation, because nothing else
+//! holds the value it would point to. These modules only choose what a `Weak` field
+//! looks like on the wire.
+//!
+//! To leave a `Weak` field out of the output entirely, use
+//! `#[serde(skip, default = "fallacy_arc::Weak::new")]` instead.
+//!
+//! [`Weak`]: crate::Weak
+
+/// Serializes a `Weak<T>` as the result of [`Weak::upgrade`], that is as an
+/// `Option<T>`, and deserializes it to a `Weak` that cannot be upgraded.
+///
+/// This is the same format as the `Serialize` and `Deserialize` impls of `Weak`, for
+/// fields where those impls aren't picked up, such as in remote derives.
+///
+/// [`Weak::upgrade`]: crate::Weak::upgrade
+pub mod weak_as_option {
+    use crate::Weak;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[inline]
+    pub fn serialize<T, S>(weak: &Weak<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        weak.upgrade().serialize(serializer)
+    }
+
+    #[inline]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Weak<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let _ = Option::<T>::deserialize(deserializer)?;
+        Ok(Weak::new())
+    }
+}
+
+/// Serializes a `Weak<T>` as a unit, without touching the value it points to, and
+/// deserializes any value to a `Weak` that cannot be upgraded.
+///
+/// This is useful for back-pointers, where serializing the target would recurse into
+/// the parent. `T` doesn't need to implement `Serialize` or `Deserialize`.
+pub mod weak_skip {
+    use crate::Weak;
+    use ::serde::de::IgnoredAny;
+    use ::serde::{Deserialize, Deserializer, Serializer};
+
+    #[inline]
+    pub fn serialize<T, S>(_weak: &Weak<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized,
+        S: Serializer,
+    {
+        serializer.serialize_unit()
+    }
+
+    #[inline]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Weak<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        IgnoredAny::deserialize(deserializer)?;
+        Ok(Weak::new())
+    }
+}
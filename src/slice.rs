@@ -0,0 +1,197 @@
+//! Conversions of `Arc<[T]>` into arrays and owned containers.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::alloc::Layout;
+use std::error::Error;
+use std::fmt;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ptr;
+use std::sync::Arc as StdArc;
+
+impl<T> Arc<[T]> {
+    /// Moves the elements of a unique `Arc<[T]>` into a new [`Vec`].
+    ///
+    /// Returns an error holding the original `Arc` if there are other `Arc` or
+    /// [`Weak`](crate::Weak) pointers to the same allocation, or if allocating the
+    /// vector fails.
+    pub fn try_into_vec(this: Self) -> Result<Vec<T>, IntoOwnedError<T>> {
+        let mut this = this;
+        if Arc::get_mut(&mut this).is_none() {
+            return Err(IntoOwnedError { arc: this, alloc: None });
+        }
+        let len = this.len();
+        let mut vec = Vec::new();
+        if vec.try_reserve_exact(len).is_err() {
+            return Err(IntoOwnedError::alloc(this));
+        }
+        // SAFETY: `this` is unique and `vec` has room for `len` elements.
+        unsafe {
+            move_elements(this, vec.as_mut_ptr());
+            vec.set_len(len);
+        }
+        Ok(vec)
+    }
+
+    /// Moves the elements of a unique `Arc<[T]>` into a new boxed slice.
+    ///
+    /// Returns an error holding the original `Arc` if there are other `Arc` or
+    /// [`Weak`](crate::Weak) pointers to the same allocation, or if allocating the
+    /// boxed slice fails.
+    pub fn try_into_boxed_slice(this: Self) -> Result<Box<[T]>, IntoOwnedError<T>> {
+        let mut this = this;
+        if Arc::get_mut(&mut this).is_none() {
+            return Err(IntoOwnedError { arc: this, alloc: None });
+        }
+        let mut boxed = match Box::<[T]>::try_new_uninit_slice(this.len()) {
+            Ok(boxed) => boxed,
+            Err(_) => return Err(IntoOwnedError::alloc(this)),
+        };
+        // SAFETY: `this` is unique and `boxed` has room for all of its elements, which
+        // are initialized by `move_elements`.
+        unsafe {
+            move_elements(this, boxed.as_mut_ptr().cast::<T>());
+            Ok(Box::from_raw(Box::into_raw(boxed) as *mut [T]))
+        }
+    }
+}
+
+impl<T, const N: usize> TryFrom<Arc<[T]>> for Arc<[T; N]> {
+    type Error = Arc<[T]>;
+
+    /// Converts an `Arc<[T]>` into an `Arc<[T; N]>` pointing to the same allocation.
+    ///
+    /// Returns the original `Arc` if its length is not `N`.
+    #[inline]
+    fn try_from(arc: Arc<[T]>) -> Result<Self, Self::Error> {
+        StdArc::<[T; N]>::try_from(arc.into_std())
+            .map(Arc::from_std)
+            .map_err(Arc::from_std)
+    }
+}
+
+/// Moves the elements of `arc` to `dst` and frees its allocation.
+///
+/// # Safety
+///
+/// `arc` must be the only pointer to its allocation, and `dst` must be valid for writes of
+/// `arc.len()` elements.
+unsafe fn move_elements<T>(arc: Arc<[T]>, dst: *mut T) {
+    let len = arc.len();
+    // `ManuallyDrop<T>` has the same layout as `T`, so dropping the cast `Arc` frees the
+    // allocation without dropping the elements again.
+    let arc = StdArc::from_raw(StdArc::into_raw(arc.into_std()) as *const [ManuallyDrop<T>]);
+    ptr::copy_nonoverlapping(arc.as_ptr().cast::<T>(), dst, len);
+}
+
+/// The error type returned when a unique `Arc<[T]>` cannot be converted to an owned
+/// container.
+///
+/// The original `Arc` can be recovered with [`into_arc`](IntoOwnedError::into_arc).
+pub struct IntoOwnedError<T> {
+    arc: Arc<[T]>,
+    alloc: Option<AllocError>,
+}
+
+impl<T> IntoOwnedError<T> {
+    #[inline]
+    fn alloc(arc: Arc<[T]>) -> Self {
+        let layout = Layout::array::<MaybeUninit<T>>(arc.len()).unwrap_or(Layout::new::<()>());
+        IntoOwnedError {
+            arc,
+            alloc: Some(AllocError::new(layout)),
+        }
+    }
+
+    /// Returns the allocation error, or [`None`] if the `Arc` was shared.
+    #[must_use]
+    #[inline]
+    pub fn alloc_error(&self) -> Option<AllocError> {
+        self.alloc
+    }
+
+    /// Returns the original `Arc`.
+    #[must_use]
+    #[inline]
+    pub fn into_arc(self) -> Arc<[T]> {
+        self.arc
+    }
+}
+
+impl<T> fmt::Debug for IntoOwnedError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoOwnedError")
+            .field("len", &self.arc.len())
+            .field("alloc", &self.alloc)
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for IntoOwnedError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.alloc {
+            Some(e) => fmt::Display::fmt(e, f),
+            None => write!(f, "the slice is shared with other pointers"),
+        }
+    }
+}
+
+impl<T> Error for IntoOwnedError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{CountingDrop, DropCounter};
+    use crate::Arc;
+    use std::sync::Arc as StdArc;
+
+    fn counted(counter: &DropCounter, len: usize) -> Arc<[CountingDrop<'_, usize>]> {
+        Arc::from_std(StdArc::from((0..len).map(|i| counter.wrap(i)).collect::<Vec<_>>()))
+    }
+
+    #[test]
+    fn try_into_vec_moves_elements() {
+        let counter = DropCounter::new();
+        let vec = Arc::try_into_vec(counted(&counter, 4)).unwrap();
+        assert_eq!(counter.dropped(), 0);
+        assert_eq!(vec.iter().map(|v| **v).collect::<Vec<_>>(), [0, 1, 2, 3]);
+        drop(vec);
+        assert_eq!(counter.created(), 4);
+        assert_eq!(counter.dropped(), 4);
+    }
+
+    #[test]
+    fn try_into_boxed_slice_moves_elements() {
+        let counter = DropCounter::new();
+        let boxed = Arc::try_into_boxed_slice(counted(&counter, 3)).unwrap();
+        assert_eq!(counter.dropped(), 0);
+        assert_eq!(boxed.len(), 3);
+        drop(boxed);
+        assert_eq!(counter.dropped(), 3);
+    }
+
+    #[test]
+    fn empty_slice() {
+        let counter = DropCounter::new();
+        assert!(Arc::try_into_vec(counted(&counter, 0)).unwrap().is_empty());
+        assert!(Arc::try_into_boxed_slice(counted(&counter, 0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn shared_slice_is_returned() {
+        let counter = DropCounter::new();
+        let arc = counted(&counter, 2);
+        let other = arc.clone();
+        let err = Arc::try_into_vec(arc).unwrap_err();
+        assert!(err.alloc_error().is_none());
+        let arc = err.into_arc();
+        assert!(Arc::ptr_eq(&arc, &other));
+        drop(other);
+
+        let weak = Arc::downgrade(&arc);
+        let arc = Arc::try_into_boxed_slice(arc).unwrap_err().into_arc();
+        drop(weak);
+        assert_eq!(counter.dropped(), 0);
+        drop(arc);
+        assert_eq!(counter.dropped(), 2);
+    }
+}
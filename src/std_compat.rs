@@ -0,0 +1,111 @@
+//! Panicking constructors with the names and conversions of std's `Arc`, for migrating
+//! code from `std::sync::Arc` one call site at a time.
+//!
+//! This module is enabled by the `std-compat` feature, which also implements the
+//! copying `From` conversions that std's `Arc` has, such as `From<&str>`. Importing [`ArcCompat`] makes `Arc::new`,
+//! `Arc::pin` and `Arc::make_mut` available. They all go through the fallible
+//! constructors of this crate and panic with the [`AllocError`] when allocation fails.
+//!
+//! Moving conversions such as `From<Vec<T>>` and `From<Box<T>>` are not provided,
+//! because there is no fallible path for them. `From<T>` is not provided either: a
+//! blanket impl would conflict with `From<Local> for Arc<Local>` impls in downstream
+//! crates, so enabling the feature anywhere in the dependency graph could break them.
+//! Use [`ArcCompat::new`] instead.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::ffi::CStr;
+use std::pin::Pin;
+
+/// The panicking constructors of std's `Arc`.
+pub trait ArcCompat<T> {
+    /// Constructs a new `Arc<T>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if allocation fails, see [`Arc::try_new`].
+    fn new(data: T) -> Self;
+
+    /// Constructs a new `Pin<Arc<T>>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if allocation fails, see [`Arc::try_pin`].
+    fn pin(data: T) -> Pin<Self>
+    where
+        Self: Sized;
+
+    /// Makes a mutable reference into the given `Arc`, cloning the inner value into a
+    /// new allocation if it is shared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if allocation fails, see [`Arc::make_mut_with`].
+    fn make_mut(this: &mut Self) -> &mut T
+    where
+        T: Clone;
+}
+
+impl<T> ArcCompat<T> for Arc<T> {
+    #[track_caller]
+    #[inline]
+    fn new(data: T) -> Self {
+        unwrap(Arc::try_new(data))
+    }
+
+    #[track_caller]
+    #[inline]
+    fn pin(data: T) -> Pin<Self> {
+        unwrap(Arc::try_pin(data))
+    }
+
+    #[track_caller]
+    #[inline]
+    fn make_mut(this: &mut Self) -> &mut T
+    where
+        T: Clone,
+    {
+        unwrap(Arc::make_mut_with(this, |data| Ok(data.clone())))
+    }
+}
+
+impl From<&str> for Arc<str> {
+    #[track_caller]
+    #[inline]
+    fn from(s: &str) -> Self {
+        unwrap(Arc::try_clone_from_ref(s))
+    }
+}
+
+impl From<String> for Arc<str> {
+    #[track_caller]
+    #[inline]
+    fn from(s: String) -> Self {
+        unwrap(Arc::try_clone_from_ref(s.as_str()))
+    }
+}
+
+impl<T: Clone> From<&[T]> for Arc<[T]> {
+    #[track_caller]
+    #[inline]
+    fn from(s: &[T]) -> Self {
+        unwrap(Arc::try_clone_from_ref(s))
+    }
+}
+
+impl From<&CStr> for Arc<CStr> {
+    #[track_caller]
+    #[inline]
+    fn from(s: &CStr) -> Self {
+        unwrap(Arc::try_clone_from_ref(s))
+    }
+}
+
+#[track_caller]
+#[inline]
+fn unwrap<T>(result: Result<T, AllocError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => panic!("{}", e),
+    }
+}
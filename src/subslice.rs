@@ -0,0 +1,276 @@
+//! Sub-slices that share the allocation of an `Arc<[T]>`.
+
+use crate::Arc;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::{Bound, Deref, Range, RangeBounds};
+
+/// An owned sub-slice of an [`Arc<[T]>`](Arc).
+///
+/// A `SubsliceArc` keeps the whole slice alive and refers to a part of it by index
+/// range, so taking sub-slices never copies or allocates. Each `SubsliceArc` is an
+/// independent handle that can be sent to another thread. Comparisons and hashing use
+/// the sub-slice content, just like `[T]`.
+pub struct SubsliceArc<T> {
+    arc: Arc<[T]>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> Arc<[T]> {
+    /// Returns an iterator over `chunk_size` elements of the slice at a time, as owned
+    /// [`SubsliceArc`]s sharing this allocation.
+    ///
+    /// The last chunk is shorter if `chunk_size` does not divide the length of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    pub fn chunks_owned(this: &Self, chunk_size: usize) -> ChunksOwned<T> {
+        SubsliceArc::new(this.clone()).chunks_owned(chunk_size)
+    }
+}
+
+impl<T> SubsliceArc<T> {
+    /// Creates a `SubsliceArc` covering the whole slice.
+    #[must_use]
+    #[inline]
+    pub fn new(arc: Arc<[T]>) -> SubsliceArc<T> {
+        let end = arc.len();
+        SubsliceArc { arc, start: 0, end }
+    }
+
+    /// Returns a sub-slice of this sub-slice, sharing the same allocation.
+    ///
+    /// `range` is relative to this sub-slice. Returns [`None`] if the range is out of
+    /// bounds, in the same cases as [`slice::get`].
+    #[must_use]
+    pub fn subslice<R: RangeBounds<usize>>(&self, range: R) -> Option<SubsliceArc<T>> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1)?,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.len() {
+            return None;
+        }
+        Some(SubsliceArc {
+            arc: self.arc.clone(),
+            start: self.start + start,
+            end: self.start + end,
+        })
+    }
+
+    /// Returns an iterator over `chunk_size` elements of this sub-slice at a time, as
+    /// owned `SubsliceArc`s sharing the same allocation.
+    ///
+    /// The last chunk is shorter if `chunk_size` does not divide the length of the
+    /// sub-slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    pub fn chunks_owned(&self, chunk_size: usize) -> ChunksOwned<T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        ChunksOwned {
+            rest: self.clone(),
+            chunk_size,
+        }
+    }
+
+    /// Returns the sub-slice as a `[T]`.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `start..end` is checked to be in bounds when the `SubsliceArc` is created.
+        unsafe { self.arc.get_unchecked(self.start..self.end) }
+    }
+
+    /// Returns the index range of the sub-slice within the whole slice.
+    #[must_use]
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the `Arc<[T]>` holding the whole slice.
+    #[must_use]
+    #[inline]
+    pub fn as_arc(&self) -> &Arc<[T]> {
+        &self.arc
+    }
+}
+
+impl<T> Clone for SubsliceArc<T> {
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    fn clone(&self) -> SubsliceArc<T> {
+        SubsliceArc {
+            arc: self.arc.clone(),
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl<T> From<Arc<[T]>> for SubsliceArc<T> {
+    #[inline]
+    fn from(arc: Arc<[T]>) -> SubsliceArc<T> {
+        SubsliceArc::new(arc)
+    }
+}
+
+impl<T> Deref for SubsliceArc<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for SubsliceArc<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Borrow<[T]> for SubsliceArc<T> {
+    #[inline]
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SubsliceArc<T> {
+    #[inline]
+    fn eq(&self, other: &SubsliceArc<T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for SubsliceArc<T> {}
+
+impl<T: PartialEq> PartialEq<[T]> for SubsliceArc<T> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<'a, T: PartialEq> PartialEq<&'a [T]> for SubsliceArc<T> {
+    #[inline]
+    fn eq(&self, other: &&'a [T]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for SubsliceArc<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &SubsliceArc<T>) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord> Ord for SubsliceArc<T> {
+    #[inline]
+    fn cmp(&self, other: &SubsliceArc<T>) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Hash> Hash for SubsliceArc<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SubsliceArc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+/// An iterator over owned chunks of a slice.
+///
+/// This struct is created by [`Arc::chunks_owned`] and [`SubsliceArc::chunks_owned`].
+pub struct ChunksOwned<T> {
+    rest: SubsliceArc<T>,
+    chunk_size: usize,
+}
+
+impl<T> Iterator for ChunksOwned<T> {
+    type Item = SubsliceArc<T>;
+
+    fn next(&mut self) -> Option<SubsliceArc<T>> {
+        let rest = &mut self.rest;
+        if rest.start == rest.end {
+            return None;
+        }
+        let end = rest.start + self.chunk_size.min(rest.len());
+        let chunk = SubsliceArc {
+            arc: rest.arc.clone(),
+            start: rest.start,
+            end,
+        };
+        rest.start = end;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.len();
+        let len = len / self.chunk_size + (len % self.chunk_size != 0) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for ChunksOwned<T> {}
+
+impl<T> FusedIterator for ChunksOwned<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for ChunksOwned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunksOwned")
+            .field("rest", &self.rest)
+            .field("chunk_size", &self.chunk_size)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, SubsliceArc};
+
+    #[test]
+    fn chunks_owned_with_huge_chunk_size() {
+        let arc = Arc::try_clone_from_ref(&[0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9][..]).unwrap();
+        let sub = SubsliceArc::new(arc).subslice(5..10).unwrap();
+        let mut chunks = sub.chunks_owned(usize::MAX);
+        let chunk = chunks.next().unwrap();
+        assert_eq!(chunk.range(), 5..10);
+        assert_eq!(chunk.as_slice(), &[5, 6, 7, 8, 9]);
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn chunks_owned_with_offset() {
+        let arc = Arc::try_clone_from_ref(&[0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9][..]).unwrap();
+        let sub = SubsliceArc::new(arc).subslice(3..10).unwrap();
+        let chunks: Vec<_> = sub.chunks_owned(3).map(|c| c.range()).collect();
+        assert_eq!(chunks, [3..6, 6..9, 9..10]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Substrings that share the allocation of an `Arc<str>`.
+
+use crate::Arc;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Bound, Deref, Range, RangeBounds};
+
+/// An owned substring of an [`Arc<str>`](Arc).
+///
+/// A `SubstrArc` keeps the whole string alive and refers to a part of it by byte range,
+/// so taking substrings never copies or allocates. Comparisons and hashing use the
+/// substring content, just like `str`.
+pub struct SubstrArc {
+    arc: Arc<str>,
+    start: usize,
+    end: usize,
+}
+
+impl SubstrArc {
+    /// Creates a `SubstrArc` covering the whole string.
+    #[must_use]
+    #[inline]
+    pub fn new(arc: Arc<str>) -> SubstrArc {
+        let end = arc.len();
+        SubstrArc { arc, start: 0, end }
+    }
+
+    /// Returns a substring of this substring, sharing the same allocation.
+    ///
+    /// `range` is relative to this substring. Returns [`None`] if the range is out of
+    /// bounds or does not lie on `char` boundaries, in the same cases as [`str::get`].
+    #[must_use]
+    pub fn substr<R: RangeBounds<usize>>(&self, range: R) -> Option<SubstrArc> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1)?,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len(),
+        };
+        self.as_str().get(start..end)?;
+        Some(SubstrArc {
+            arc: self.arc.clone(),
+            start: self.start + start,
+            end: self.start + end,
+        })
+    }
+
+    /// Returns the substring that corresponds to `subset`, which must be a slice of this
+    /// substring, for example one produced by [`str::split`].
+    ///
+    /// Returns [`None`] if `subset` does not point into this substring, or if it is an
+    /// empty string that does not lie on a `char` boundary of this substring.
+    #[must_use]
+    pub fn slice_ref(&self, subset: &str) -> Option<SubstrArc> {
+        let base = self.as_str().as_ptr() as usize;
+        let offset = (subset.as_ptr() as usize).checked_sub(base)?;
+        let end = offset.checked_add(subset.len())?;
+        // A non-empty `str` starts and ends on `char` boundaries, but an empty one can
+        // point into the middle of a `char`.
+        self.as_str().get(offset..end)?;
+        Some(SubstrArc {
+            arc: self.arc.clone(),
+            start: self.start + offset,
+            end: self.start + end,
+        })
+    }
+
+    /// Returns the substring as a `str`.
+    #[must_use]
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: `start..end` is checked to be in bounds and on `char` boundaries when
+        // the `SubstrArc` is created.
+        unsafe { self.arc.get_unchecked(self.start..self.end) }
+    }
+
+    /// Returns the byte range of the substring within the whole string.
+    #[must_use]
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the `Arc<str>` holding the whole string.
+    #[must_use]
+    #[inline]
+    pub fn as_arc(&self) -> &Arc<str> {
+        &self.arc
+    }
+}
+
+impl Clone for SubstrArc {
+    #[cfg_attr(feature = "watermark", track_caller)]
+    #[inline]
+    fn clone(&self) -> SubstrArc {
+        SubstrArc {
+            arc: self.arc.clone(),
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl From<Arc<str>> for SubstrArc {
+    #[inline]
+    fn from(arc: Arc<str>) -> SubstrArc {
+        SubstrArc::new(arc)
+    }
+}
+
+impl Deref for SubstrArc {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SubstrArc {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for SubstrArc {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for SubstrArc {
+    #[inline]
+    fn eq(&self, other: &SubstrArc) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SubstrArc {}
+
+impl PartialEq<str> for SubstrArc {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for SubstrArc {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for SubstrArc {
+    #[inline]
+    fn partial_cmp(&self, other: &SubstrArc) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SubstrArc {
+    #[inline]
+    fn cmp(&self, other: &SubstrArc) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for SubstrArc {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl fmt::Display for SubstrArc {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Debug for SubstrArc {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, SubstrArc};
+    use std::ops::Bound;
+
+    fn substr(s: &str) -> SubstrArc {
+        SubstrArc::new(Arc::try_clone_from_ref(s).unwrap())
+    }
+
+    #[test]
+    fn nested_substr_ranges() {
+        let s = substr("hello, world");
+        let world = s.substr(7..).unwrap();
+        assert_eq!(world, "world");
+        assert_eq!(world.range(), 7..12);
+        let orl = world.substr(1..=3).unwrap();
+        assert_eq!(orl, "orl");
+        assert_eq!(orl.range(), 8..11);
+        assert_eq!(world.substr(..).unwrap(), "world");
+        assert!(world.substr(3..6).is_none());
+        assert!(world.substr((Bound::Included(4), Bound::Excluded(3))).is_none());
+        assert!(Arc::ptr_eq(orl.as_arc(), s.as_arc()));
+    }
+
+    #[test]
+    fn substr_rejects_non_char_boundaries() {
+        let s = substr("aé");
+        assert!(s.substr(2..).is_none());
+        assert!(s.substr(..2).is_none());
+        assert_eq!(s.substr(1..).unwrap(), "é");
+    }
+
+    #[test]
+    fn slice_ref_of_split() {
+        let s = substr("a,bc,d").substr(2..).unwrap();
+        let parts: Vec<_> = s.split(',').map(|part| s.slice_ref(part).unwrap()).collect();
+        assert_eq!(parts, ["bc", "d"]);
+        assert_eq!(parts[0].range(), 2..4);
+        assert_eq!(parts[1].range(), 5..6);
+    }
+
+    #[test]
+    fn slice_ref_rejects_foreign_strings() {
+        let s = substr("abc");
+        let whole = s.as_arc().clone();
+        let tail = s.substr(1..).unwrap();
+        assert!(tail.slice_ref(&whole[..1]).is_none());
+        assert!(tail.slice_ref(&whole[1..]).is_some());
+        assert!(s.substr(..2).unwrap().slice_ref(&whole[1..]).is_none());
+        assert!(s.slice_ref("abc").is_none());
+    }
+
+    #[test]
+    fn slice_ref_rejects_empty_str_inside_char() {
+        let s = substr("é");
+        let bytes = s.as_bytes();
+        let inside = std::str::from_utf8(&bytes[1..1]).unwrap();
+        assert!(s.slice_ref(inside).is_none());
+        let at_end = std::str::from_utf8(&bytes[2..2]).unwrap();
+        assert_eq!(s.slice_ref(at_end).unwrap().range(), 2..2);
+    }
+}
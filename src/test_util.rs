@@ -0,0 +1,196 @@
+//! Helpers for testing the lifetime of shared values.
+//!
+//! [`DropCounter`] and [`CountingDrop`] track how many wrapped values are alive, and
+//! the [`assert_live_objects!`] and [`assert_no_leaks!`] macros check them.
+//! [`CountingAllocator`] counts the allocations made by each thread, which for the
+//! default test harness means by each test.
+//!
+//! [`assert_live_objects!`]: crate::assert_live_objects
+//! [`assert_no_leaks!`]: crate::assert_no_leaks
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the values wrapped in [`CountingDrop`] that are created and dropped.
+///
+/// `DropCounter::new` is a `const fn`, so a counter can be declared as a `static` for
+/// values that are moved to other threads.
+#[derive(Debug, Default)]
+pub struct DropCounter {
+    created: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+impl DropCounter {
+    /// Creates a new `DropCounter` with no values.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> DropCounter {
+        DropCounter {
+            created: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+        }
+    }
+
+    /// Wraps `value` so that it is counted by this counter.
+    #[inline]
+    pub fn wrap<T>(&self, value: T) -> CountingDrop<'_, T> {
+        self.created.fetch_add(1, Ordering::Relaxed);
+        CountingDrop { value, counter: self }
+    }
+
+    /// Returns the number of values that have been created.
+    #[must_use]
+    #[inline]
+    pub fn created(&self) -> usize {
+        self.created.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of values that have been dropped.
+    #[must_use]
+    #[inline]
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of values that are still alive.
+    #[must_use]
+    #[inline]
+    pub fn live(&self) -> usize {
+        let dropped = self.dropped();
+        self.created() - dropped
+    }
+}
+
+/// A value whose creation and drop are counted by a [`DropCounter`].
+///
+/// Cloning a `CountingDrop` counts as creating a new value.
+pub struct CountingDrop<'a, T> {
+    value: T,
+    counter: &'a DropCounter,
+}
+
+impl<T> Drop for CountingDrop<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.counter.dropped.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl<T: Clone> Clone for CountingDrop<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.counter.wrap(self.value.clone())
+    }
+}
+
+impl<T> Deref for CountingDrop<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for CountingDrop<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CountingDrop<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for CountingDrop<'_, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for CountingDrop<'_, T> {}
+
+impl<T: Hash> Hash for CountingDrop<'_, T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A global allocator that counts the allocations made by each thread.
+///
+/// Install it in a test binary with `#[global_allocator]` and read the counts with
+/// [`thread_allocations`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CountingAllocator<A = System>(pub A);
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        self.0.alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        self.0.alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        self.0.realloc(ptr, layout, new_size)
+    }
+}
+
+#[inline]
+fn count_allocation() {
+    // Allocations made while the thread is being torn down are not counted.
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+/// Returns the number of allocations made by the current thread through
+/// [`CountingAllocator`].
+#[must_use]
+#[inline]
+pub fn thread_allocations() -> usize {
+    ALLOCATIONS.try_with(Cell::get).unwrap_or(0)
+}
+
+/// Asserts that a [`DropCounter`] has exactly the expected number of live values.
+#[macro_export]
+macro_rules! assert_live_objects {
+    ($counter:expr, $expected:expr $(,)?) => {{
+        let live = $crate::test_util::DropCounter::live(&$counter);
+        let expected: usize = $expected;
+        assert_eq!(live, expected, "unexpected number of live objects");
+    }};
+}
+
+/// Asserts that a [`DropCounter`] has no live values.
+#[macro_export]
+macro_rules! assert_no_leaks {
+    ($counter:expr $(,)?) => {
+        $crate::assert_live_objects!($counter, 0)
+    };
+}
@@ -0,0 +1,96 @@
+//! A registry of shared values keyed by their type.
+
+use crate::Arc;
+use fallacy_alloc::AllocError;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc as StdArc;
+use std::sync::{PoisonError, RwLock};
+
+/// A concurrent map that holds at most one shared value of each type.
+///
+/// Values are stored as `Arc<dyn Any + Send + Sync>` keyed by their [`TypeId`],
+/// and are handed out again as typed [`Arc`] pointers.
+#[derive(Debug, Default)]
+pub struct ArcTypeMap {
+    map: RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl ArcTypeMap {
+    /// Creates an empty `ArcTypeMap`, without allocating any memory.
+    #[must_use]
+    #[inline]
+    pub fn new() -> ArcTypeMap {
+        ArcTypeMap::default()
+    }
+
+    /// Moves `value` into a new [`Arc`] and inserts it into the map, returning an error
+    /// if allocation fails.
+    ///
+    /// If the map already held a value of type `T`, it is replaced and returned.
+    #[inline]
+    pub fn try_insert<T>(&self, value: T) -> Result<Option<Arc<T>>, AllocError>
+    where
+        T: Any + Send + Sync,
+    {
+        self.try_insert_arc(Arc::try_new(value)?)
+    }
+
+    /// Inserts an existing [`Arc`] into the map, returning an error if growing the map fails.
+    ///
+    /// If the map already held a value of type `T`, it is replaced and returned.
+    pub fn try_insert_arc<T>(&self, value: Arc<T>) -> Result<Option<Arc<T>>, AllocError>
+    where
+        T: Any + Send + Sync,
+    {
+        let value: StdArc<dyn Any + Send + Sync> = value.into_std();
+        let mut map = self.map.write().unwrap_or_else(PoisonError::into_inner);
+        map.try_reserve(1)?;
+        // Does not grow the map, the capacity was reserved above.
+        #[allow(clippy::disallowed_methods)]
+        let old = map.insert(TypeId::of::<T>(), Arc::from_std(value));
+        Ok(old.and_then(|old| old.downcast().ok()))
+    }
+
+    /// Returns the value of type `T`, if there is one.
+    #[must_use]
+    pub fn get<T>(&self) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        let map = self.map.read().unwrap_or_else(PoisonError::into_inner);
+        let value = map.get(&TypeId::of::<T>())?.clone();
+        value.downcast().ok()
+    }
+
+    /// Removes the value of type `T` from the map, returning it if there was one.
+    pub fn remove<T>(&self) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        let mut map = self.map.write().unwrap_or_else(PoisonError::into_inner);
+        map.remove(&TypeId::of::<T>())?.downcast().ok()
+    }
+
+    /// Returns `true` if the map holds a value of type `T`.
+    #[must_use]
+    pub fn contains<T>(&self) -> bool
+    where
+        T: Any + Send + Sync,
+    {
+        let map = self.map.read().unwrap_or_else(PoisonError::into_inner);
+        map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns the number of values in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns `true` if the map holds no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
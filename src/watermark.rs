@@ -0,0 +1,226 @@
+//! Warnings for runaway strong counts.
+//!
+//! When the `watermark` feature is enabled, cloning an [`Arc`](crate::Arc) checks the
+//! new strong count against a global watermark, and reports the clone that pushes the
+//! count over it. The report includes the payload type and the location of the clone,
+//! which usually points at the source of a reference leak.
+//!
+//! The global watermark can be overridden for single payload types with
+//! [`set_type_watermark`]. Strong counts created by [`Weak::upgrade`],
+//! [`Arc::increment_strong_count`] and [`Arc::clone_from_inner`] are checked as well.
+//!
+//! # Limitations
+//!
+//! Only the strong count that lands exactly on the watermark plus one is reported, so a
+//! runaway count produces one event per crossing instead of one per clone. The count is
+//! read back after it has been incremented, so when several threads clone the same value
+//! at the same moment they can all read a higher count and the crossing is missed. It is
+//! reported the next time the count crosses the watermark again.
+//!
+//! Type watermarks are published as an immutable snapshot, so checking them does not
+//! take a lock, but once any is set every check compares the payload type name against
+//! each of them. Keep the number of type watermarks small.
+//!
+//! There is no per-allocation watermark, because the allocation header belongs to std's
+//! `Arc` and has no room for one.
+//!
+//! [`Arc::increment_strong_count`]: crate::Arc::increment_strong_count
+//! [`Arc::clone_from_inner`]: crate::Arc::clone_from_inner
+//! [`Weak::upgrade`]: crate::Weak::upgrade
+
+use fallacy_alloc::AllocError;
+use std::alloc::Layout;
+use std::mem;
+use std::panic::Location;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+type Handler = fn(&WatermarkEvent);
+type TypeWatermarks = Vec<(&'static str, usize)>;
+
+static WATERMARK: AtomicUsize = AtomicUsize::new(usize::MAX);
+static HANDLER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+// Snapshots are never modified or freed once they are published, so readers don't need
+// to synchronize with writers beyond loading the pointer.
+static TYPE_WATERMARKS: AtomicPtr<TypeWatermarks> = AtomicPtr::new(ptr::null_mut());
+// The crate needs nightly anyway, regardless of `rust-version`.
+#[allow(clippy::incompatible_msrv)]
+static TYPE_WATERMARKS_WRITE: Mutex<()> = Mutex::new(());
+
+/// Describes a clone that pushed a strong count over the watermark.
+#[derive(Debug, Copy, Clone)]
+pub struct WatermarkEvent {
+    type_name: &'static str,
+    strong_count: usize,
+    location: &'static Location<'static>,
+}
+
+impl WatermarkEvent {
+    /// Returns the name of the payload type.
+    #[must_use]
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the strong count right after the clone.
+    #[must_use]
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.strong_count
+    }
+
+    /// Returns the location of the clone.
+    #[must_use]
+    #[inline]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+/// Sets the strong count above which clones are reported.
+///
+/// Pass `usize::MAX` to disable the check, which is the default.
+#[inline]
+pub fn set_strong_count_watermark(watermark: usize) {
+    WATERMARK.store(watermark, Ordering::Relaxed);
+}
+
+/// Returns the current strong count watermark.
+#[must_use]
+#[inline]
+pub fn strong_count_watermark() -> usize {
+    WATERMARK.load(Ordering::Relaxed)
+}
+
+/// Sets the strong count above which clones of `Arc<T>` are reported, overriding the
+/// global watermark for `T`, and returns an error if allocation fails.
+///
+/// Types are told apart by [`std::any::type_name`], so the watermark also applies to
+/// other types with the same name. Pass `usize::MAX` to disable the check for `T`.
+///
+/// Each call publishes a new copy of the type watermarks and leaks the previous one,
+/// which checks on other threads may still be reading, so this is meant to be called a
+/// few times during setup.
+pub fn set_type_watermark<T: ?Sized>(watermark: usize) -> Result<(), AllocError> {
+    let name = std::any::type_name::<T>();
+    let _guard = TYPE_WATERMARKS_WRITE.lock().unwrap_or_else(PoisonError::into_inner);
+    let old = match type_watermarks() {
+        Some(old) => &old[..],
+        None => &[],
+    };
+    let mut new = TypeWatermarks::new();
+    new.try_reserve_exact(old.len() + 1)?;
+    // Cannot fail, the capacity was reserved above.
+    for &entry in old.iter().filter(|(n, _)| *n != name) {
+        let _ = new.push_within_capacity(entry);
+    }
+    let _ = new.push_within_capacity((name, watermark));
+    let new = Box::try_new(new).map_err(|_| AllocError::new(Layout::new::<TypeWatermarks>()))?;
+    TYPE_WATERMARKS.store(Box::into_raw(new), Ordering::Release);
+    Ok(())
+}
+
+/// Returns the strong count watermark that applies to `Arc<T>`.
+///
+/// This is the watermark set by [`set_type_watermark`] for `T`, or the global watermark
+/// if there is none.
+#[must_use]
+pub fn type_watermark<T: ?Sized>() -> usize {
+    if let Some(watermarks) = type_watermarks() {
+        let name = std::any::type_name::<T>();
+        if let Some(&(_, watermark)) = watermarks.iter().find(|(n, _)| *n == name) {
+            return watermark;
+        }
+    }
+    strong_count_watermark()
+}
+
+#[inline]
+fn type_watermarks() -> Option<&'static TypeWatermarks> {
+    let watermarks = TYPE_WATERMARKS.load(Ordering::Acquire);
+    // SAFETY: non-null values are published by `set_type_watermark` and never freed.
+    unsafe { watermarks.as_ref() }
+}
+
+/// Sets the function called when a clone pushes a strong count over the watermark.
+///
+/// By default, the event panics in debug builds and is ignored in release builds.
+#[inline]
+pub fn set_watermark_handler(handler: fn(&WatermarkEvent)) {
+    HANDLER.store(handler as *mut (), Ordering::Release);
+}
+
+/// Reports `strong_count` if it has just crossed the watermark.
+///
+/// Only the clone that reaches `watermark + 1` is reported, see the limitations in the
+/// module documentation.
+#[track_caller]
+#[inline]
+pub(crate) fn check<T: ?Sized>(strong_count: usize) {
+    if type_watermark::<T>().checked_add(1) == Some(strong_count) {
+        report(WatermarkEvent {
+            type_name: std::any::type_name::<T>(),
+            strong_count,
+            location: Location::caller(),
+        });
+    }
+}
+
+#[cold]
+fn report(event: WatermarkEvent) {
+    let handler = HANDLER.load(Ordering::Acquire);
+    if handler.is_null() {
+        debug_assert!(
+            false,
+            "strong count of `Arc<{}>` reached {} at {}",
+            event.type_name, event.strong_count, event.location
+        );
+    } else {
+        // SAFETY: non-null values are only ever stored by `set_watermark_handler`.
+        let handler: Handler = unsafe { mem::transmute(handler) };
+        handler(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Arc;
+
+    static EVENTS: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_events(event: &WatermarkEvent) {
+        if event.type_name().ends_with("Watched") {
+            EVENTS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    struct Watched;
+
+    #[test]
+    fn type_watermark_reports_each_crossing() {
+        set_watermark_handler(count_events);
+        set_type_watermark::<Watched>(2).unwrap();
+        assert_eq!(type_watermark::<Watched>(), 2);
+        assert_eq!(type_watermark::<u8>(), strong_count_watermark());
+        set_type_watermark::<Watched>(3).unwrap();
+        set_type_watermark::<Watched>(2).unwrap();
+        assert_eq!(type_watermark::<Watched>(), 2);
+
+        let a = Arc::try_new(Watched).unwrap();
+        let b = a.clone();
+        assert_eq!(EVENTS.load(Ordering::Relaxed), 0);
+        let c = a.clone();
+        assert_eq!(EVENTS.load(Ordering::Relaxed), 1);
+        let d = a.clone();
+        assert_eq!(EVENTS.load(Ordering::Relaxed), 1);
+        drop((c, d));
+
+        let weak = Arc::downgrade(&a);
+        let c = weak.upgrade().unwrap();
+        assert_eq!(EVENTS.load(Ordering::Relaxed), 2);
+        drop((b, c));
+    }
+}
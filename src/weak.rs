@@ -1,5 +1,10 @@
 use crate::arc::Arc;
+use fallacy_alloc::AllocError;
 use std::fmt;
+use std::iter::{Enumerate, FusedIterator};
+use std::marker::Unsize;
+use std::ops::{CoerceUnsized, DispatchFromDyn};
+use std::slice;
 use std::sync::Weak as StdWeak;
 
 /// `Weak` is a version of [`Arc`] that holds a non-owning reference to the
@@ -22,10 +27,13 @@ use std::sync::Weak as StdWeak;
 /// The typical way to obtain a `Weak` pointer is to call [`Arc::downgrade`].
 ///
 /// [`upgrade`]: Weak::upgrade
-#[derive(Clone, Default)]
 #[repr(transparent)]
 pub struct Weak<T: ?Sized>(StdWeak<T>);
 
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<Weak<U>> for Weak<T> {}
+
+impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Weak<U>> for Weak<T> {}
+
 impl<T> Weak<T> {
     /// Constructs a new `Weak<T>`, without allocating any memory.
     /// Calling [`upgrade`] on the return value always gives [`None`].
@@ -54,9 +62,45 @@ impl<T: ?Sized> Weak<T> {
     /// Returns [`None`] if the inner value has since been dropped.
     #[must_use = "this returns a new `Arc`, \
                   without modifying the original weak pointer"]
+    #[cfg_attr(feature = "watermark", track_caller)]
     #[inline]
     pub fn upgrade(&self) -> Option<Arc<T>> {
-        self.0.upgrade().map(Arc::from_std)
+        let arc = self.0.upgrade()?;
+        #[cfg(feature = "watermark")]
+        crate::watermark::check::<T>(std::sync::Arc::strong_count(&arc));
+        Some(Arc::from_std(arc))
+    }
+
+    /// Attempts to upgrade every `Weak` pointer in `weaks` to an [`Arc`], returning an
+    /// error if allocating the result fails.
+    ///
+    /// The result has one entry per `Weak`, in the same order, which is [`None`] where
+    /// the inner value has since been dropped.
+    ///
+    /// This is a convenience wrapper that calls [`Weak::upgrade`] on every entry, so each
+    /// entry costs as much as a single upgrade; only the result is allocated once up
+    /// front. Use [`Weak::upgrade_live`] to skip expired entries without allocating.
+    #[inline]
+    pub fn upgrade_batch(weaks: &[Weak<T>]) -> Result<Vec<Option<Arc<T>>>, AllocError> {
+        let mut arcs = Vec::new();
+        arcs.try_reserve_exact(weaks.len())?;
+        for weak in weaks {
+            // Cannot fail, the capacity was reserved above.
+            let _ = arcs.push_within_capacity(weak.upgrade());
+        }
+        Ok(arcs)
+    }
+
+    /// Returns an iterator over the `Weak` pointers in `weaks` that can still be
+    /// upgraded, yielding each index together with the upgraded [`Arc`].
+    ///
+    /// This is a convenience for calling [`Weak::upgrade`] on every entry and skipping
+    /// the [`None`]s, and costs the same. The iterator does not allocate.
+    #[inline]
+    pub fn upgrade_live(weaks: &[Weak<T>]) -> UpgradeLive<'_, T> {
+        UpgradeLive {
+            iter: weaks.iter().enumerate(),
+        }
     }
 
     /// Gets the number of strong (`Arc`) pointers pointing to this allocation.
@@ -95,7 +139,61 @@ impl<T: ?Sized> Weak<T> {
     }
 }
 
-impl<T: ?Sized + fmt::Debug> fmt::Debug for Weak<T> {
+/// An iterator over the live entries of a slice of [`Weak`] pointers.
+///
+/// This struct is created by [`Weak::upgrade_live`].
+pub struct UpgradeLive<'a, T: ?Sized> {
+    iter: Enumerate<slice::Iter<'a, Weak<T>>>,
+}
+
+impl<T: ?Sized> Iterator for UpgradeLive<'_, T> {
+    type Item = (usize, Arc<T>);
+
+    #[cfg_attr(feature = "watermark", track_caller)]
+    fn next(&mut self) -> Option<(usize, Arc<T>)> {
+        // Not `find_map`, so that `track_caller` reaches `upgrade`.
+        for (i, weak) in &mut self.iter {
+            if let Some(arc) = weak.upgrade() {
+                return Some((i, arc));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<T: ?Sized> FusedIterator for UpgradeLive<'_, T> {}
+
+impl<T: ?Sized> fmt::Debug for UpgradeLive<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpgradeLive").finish_non_exhaustive()
+    }
+}
+
+impl<T: ?Sized> Clone for Weak<T> {
+    /// Makes a clone of the `Weak` pointer that points to the same allocation.
+    #[inline]
+    fn clone(&self) -> Weak<T> {
+        Weak(self.0.clone())
+    }
+}
+
+impl<T> Default for Weak<T> {
+    /// Constructs a new `Weak<T>`, without allocating any memory.
+    /// Calling [`upgrade`] on the return value always gives [`None`].
+    ///
+    /// [`upgrade`]: Weak::upgrade
+    #[inline]
+    fn default() -> Weak<T> {
+        Weak::new()
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Weak<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&self.0, f)
@@ -135,3 +233,65 @@ mod serde {
         }
     }
 }
+
+#[cfg(feature = "schemars")]
+mod schemars {
+    use crate::{Arc, Weak};
+    use schemars::{JsonSchema, Schema, SchemaGenerator};
+    use std::borrow::Cow;
+
+    /// A `Weak<T>` is serialized as the result of [`Weak::upgrade`], so its schema is the
+    /// schema of `Option<Arc<T>>`.
+    impl<T> JsonSchema for Weak<T>
+    where
+        T: ?Sized + JsonSchema,
+    {
+        #[inline]
+        fn inline_schema() -> bool {
+            Option::<Arc<T>>::inline_schema()
+        }
+
+        #[inline]
+        fn schema_name() -> Cow<'static, str> {
+            Option::<Arc<T>>::schema_name()
+        }
+
+        #[inline]
+        fn schema_id() -> Cow<'static, str> {
+            Option::<Arc<T>>::schema_id()
+        }
+
+        #[inline]
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            Option::<Arc<T>>::json_schema(generator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::DropCounter;
+    use crate::{Arc, Weak};
+
+    #[test]
+    fn upgrade_batch_and_live() {
+        let counter = DropCounter::new();
+        let a = Arc::try_new(counter.wrap(0)).unwrap();
+        let b = Arc::try_new(counter.wrap(1)).unwrap();
+        let c = Arc::try_new(counter.wrap(2)).unwrap();
+        let weaks = [Arc::downgrade(&a), Weak::new(), Arc::downgrade(&b), Arc::downgrade(&c)];
+        drop(b);
+
+        let batch = Weak::upgrade_batch(&weaks).unwrap();
+        let live: Vec<_> = batch.iter().map(Option::is_some).collect();
+        assert_eq!(live, [true, false, false, true]);
+        drop(batch);
+
+        let live: Vec<_> = Weak::upgrade_live(&weaks).map(|(i, arc)| (i, **arc)).collect();
+        assert_eq!(live, [(0, 0), (3, 2)]);
+        assert_eq!(Arc::strong_count(&a), 1);
+        drop((a, c));
+        assert_eq!(Weak::upgrade_live(&weaks).count(), 0);
+        assert_eq!(counter.live(), 0);
+    }
+}